@@ -1,5 +1,7 @@
 use itertools::Itertools;
 use note_pen::prelude::*;
+use note_pen::progression::ProgressionGenerator;
+use rand::SeedableRng;
 
 pub struct Solution {
     pub soprano: Vec<Note>,
@@ -16,17 +18,9 @@ fn get_solution_score(key: &Key, solution: &Solution) -> i32 {
             .iter()
             .zip(line.iter().skip(1))
             .map(|(a, b)| {
-                let mut a_scale_degree = ScaleDegree::from_note(a, key);
-                let mut b_scale_degree = ScaleDegree::from_note(b, key);
-                while a_scale_degree.is_none() {
-                    a_scale_degree = ScaleDegree::from_note(&a.increment_by(1), key);
-                }
-                while b_scale_degree.is_none() {
-                    b_scale_degree = ScaleDegree::from_note(&b.increment_by(1), key);
-                }
-                (a_scale_degree.unwrap().degree.get() as i32
-                    - b_scale_degree.unwrap().degree.get() as i32)
-                    .abs()
+                let a_scale_degree = ScaleDegree::from_note(a, key).unwrap();
+                let b_scale_degree = ScaleDegree::from_note(b, key).unwrap();
+                (a_scale_degree.degree.get() as i32 - b_scale_degree.degree.get() as i32).abs()
             })
             .map(|diff| diff.pow(2).min(1))
             .sum::<i32>();
@@ -80,14 +74,15 @@ fn get_solutions(key: &Key, progression: &[RomanNumeral]) -> Vec<Solution> {
 
 fn main() {
     let g_major = Key::new_major(Note::new(Alphabet::G, Accidental::Natural, 4)).unwrap();
-    let chords = vec![
-        RomanNumeral::major_chord(1, Inversion::ROOT),
-        RomanNumeral::major_chord(5, Inversion::FIRST),
-        RomanNumeral::major_chord(4, Inversion::FIRST),
-        RomanNumeral::major_chord(6, Inversion::ROOT),
-        RomanNumeral::seventh_chord(5, Tonality::Major, Inversion::ROOT),
-        RomanNumeral::major_chord(1, Inversion::ROOT),
-    ];
+    let generator = ProgressionGenerator {
+        inversions: true,
+        sevenths: true,
+        ..ProgressionGenerator::new(6)
+    };
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let chords = generator
+        .generate(&g_major, &mut rng)
+        .expect("six chords hold a cadence");
 
     let solutions = get_solutions(&g_major, &chords);
     let mut best_score = i32::MAX;
@@ -12,12 +12,13 @@ use std::ops::Add;
 /// assert_eq!(next_note, Note::new(Alphabet::A, Accidental::DoubleSharp, 4));
 /// assert_eq!(sharp + note, Note::new(Alphabet::B, Accidental::default(), 4));
 /// ```
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Accidental {
     None,
     DoubleFlat,
     Flat,
+    #[default]
     Natural,
     Sharp,
     DoubleSharp,
@@ -34,11 +35,30 @@ impl Accidental {
             Self::DoubleSharp => "𝄪",
         }
     }
-}
 
-impl Default for Accidental {
-    fn default() -> Self {
-        Self::Natural
+    /// Returns the number of half-steps the accidental moves a note by.
+    pub const fn offset(&self) -> i8 {
+        match self {
+            Self::None | Self::Natural => 0,
+            Self::DoubleFlat => -2,
+            Self::Flat => -1,
+            Self::Sharp => 1,
+            Self::DoubleSharp => 2,
+        }
+    }
+
+    /// Returns the accidental that moves a note by the given number of half-steps, if there is one.
+    ///
+    /// An offset of 0 is [`Accidental::Natural`].
+    pub const fn from_offset(offset: i8) -> Option<Self> {
+        match offset {
+            -2 => Some(Self::DoubleFlat),
+            -1 => Some(Self::Flat),
+            0 => Some(Self::Natural),
+            1 => Some(Self::Sharp),
+            2 => Some(Self::DoubleSharp),
+            _ => None,
+        }
     }
 }
 
@@ -65,4 +65,54 @@ impl Alphabet {
             Alphabet::G => Alphabet::A,
         }
     }
+
+    /// Returns the previous letter in the alphabet, and A.prev() becomes G.
+    pub const fn prev(&self) -> Self {
+        match self {
+            Alphabet::A => Alphabet::G,
+            Alphabet::B => Alphabet::A,
+            Alphabet::C => Alphabet::B,
+            Alphabet::D => Alphabet::C,
+            Alphabet::E => Alphabet::D,
+            Alphabet::F => Alphabet::E,
+            Alphabet::G => Alphabet::F,
+        }
+    }
+
+    /// Returns the position of the letter in the alphabet, with A as 0.
+    pub const fn index(&self) -> u8 {
+        match self {
+            Alphabet::A => 0,
+            Alphabet::B => 1,
+            Alphabet::C => 2,
+            Alphabet::D => 3,
+            Alphabet::E => 4,
+            Alphabet::F => 5,
+            Alphabet::G => 6,
+        }
+    }
+
+    /// Returns the letter at the given position, wrapping around after G.
+    pub const fn from_index(index: u8) -> Self {
+        match index % 7 {
+            0 => Alphabet::A,
+            1 => Alphabet::B,
+            2 => Alphabet::C,
+            3 => Alphabet::D,
+            4 => Alphabet::E,
+            5 => Alphabet::F,
+            _ => Alphabet::G,
+        }
+    }
+
+    /// Moves the letter by the given number of steps, wrapping in both directions.
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// assert_eq!(Alphabet::C.shift(2), Alphabet::E);
+    /// assert_eq!(Alphabet::C.shift(-3), Alphabet::G);
+    /// ```
+    pub const fn shift(&self, steps: i64) -> Self {
+        Self::from_index((self.index() as i64 + steps).rem_euclid(7) as u8)
+    }
 }
@@ -0,0 +1,677 @@
+//! Harmonic analysis of chords and scores.
+
+use crate::chord::{Chord, Inversion};
+use crate::duration::Fraction;
+use crate::key::Key;
+use crate::measure::TimedMeasureItemInner;
+use crate::note::Note;
+use crate::pitch::RelativePitch;
+use crate::roman_numeral::{NamedChord, RomanNumeral};
+use crate::score::Score;
+use crate::time::{Beat, Measure};
+use crate::{Accidental, Tonality};
+use std::collections::{HashMap, HashSet};
+
+/// The role a chord plays in tonic–predominant–dominant harmony.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HarmonicFunction {
+    Tonic,
+    Predominant,
+    Dominant,
+}
+
+impl HarmonicFunction {
+    /// Returns the usual function of a diatonic scale degree, or `None` for the mediant.
+    pub const fn of_degree(degree: u8) -> Option<Self> {
+        match degree {
+            1 | 6 => Some(Self::Tonic),
+            2 | 4 => Some(Self::Predominant),
+            5 | 7 => Some(Self::Dominant),
+            _ => None,
+        }
+    }
+}
+
+/// The kinds of cadences that close a phrase.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cadence {
+    /// V–I with both chords in root position.
+    PerfectAuthentic,
+    /// V–I with an inverted dominant or tonic, or vii°–I.
+    ImperfectAuthentic,
+    /// Any phrase ending on V.
+    Half,
+    /// iv6–V in a minor key.
+    PhrygianHalf,
+    /// IV–I.
+    Plagal,
+    /// V–vi.
+    Deceptive,
+}
+
+/// A possible roman numeral for a chord.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interpretation {
+    pub numeral: RomanNumeral,
+    /// How unlikely the interpretation is, where 0 is a complete diatonic chord spelled as expected.
+    pub cost: u32,
+}
+
+/// Finds the roman numerals that describe a chord in a key, best first.
+///
+/// Diatonic chords are preferred over borrowed chords, which are preferred over applied and named chords.
+/// Chords that are missing their fifth are still recognized, and the spelling of the notes is used
+/// to tell enharmonic chords apart, like a German sixth and the dominant seventh of the Neapolitan.
+/// The inversion is taken from the lowest note.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::analysis::analyze_chord;
+/// use note_pen::prelude::*;
+/// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+/// let chord = Note::new(Alphabet::F, Accidental::Sharp, 4)
+///     + Note::new(Alphabet::A, Accidental::Natural, 5)
+///     + Note::new(Alphabet::C, Accidental::Natural, 5)
+///     + Note::new(Alphabet::D, Accidental::Natural, 5);
+/// let interpretations = analyze_chord(&chord, &key);
+/// assert_eq!(interpretations[0].numeral.to_string(), "V65/V");
+/// ```
+pub fn analyze_chord(chord: &Chord, key: &Key) -> Vec<Interpretation> {
+    let pitches = chord
+        .notes
+        .iter()
+        .map(|note| note.id().simple())
+        .collect::<HashSet<_>>();
+    let Some(bass) = chord.notes.iter().min_by_key(|note| note.id()) else {
+        return vec![];
+    };
+    if pitches.len() < 2 {
+        return vec![];
+    }
+    let minor = key.tonality() == Some(Tonality::Minor);
+    let mut best: HashMap<String, Interpretation> = HashMap::new();
+    for (numeral, base_cost) in candidates(minor) {
+        let Some(interpretation) = fit(chord, &pitches, bass, key, numeral, base_cost) else {
+            continue;
+        };
+        let name = interpretation.numeral.to_string();
+        match best.get(&name) {
+            Some(existing) if existing.cost <= interpretation.cost => {}
+            _ => {
+                best.insert(name, interpretation);
+            }
+        }
+    }
+    let mut interpretations = best.into_iter().collect::<Vec<_>>();
+    interpretations
+        .sort_by(|(a_name, a), (b_name, b)| a.cost.cmp(&b.cost).then_with(|| a_name.cmp(b_name)));
+    interpretations
+        .into_iter()
+        .map(|(_, interpretation)| interpretation)
+        .collect()
+}
+
+/// Compares a chord against a candidate, returning the inverted numeral and its cost.
+fn fit(
+    chord: &Chord,
+    pitches: &HashSet<RelativePitch>,
+    bass: &Note,
+    key: &Key,
+    numeral: RomanNumeral,
+    base_cost: u32,
+) -> Option<Interpretation> {
+    let realized = RomanNumeral {
+        inversion: Inversion::ROOT,
+        ..numeral.clone()
+    }
+    .chord(key);
+    let realized_pitches = realized
+        .notes
+        .iter()
+        .map(|note| note.id().simple())
+        .collect::<Vec<_>>();
+    if !pitches.iter().all(|pitch| realized_pitches.contains(pitch)) {
+        return None;
+    }
+    let mut cost = base_cost;
+    for (i, pitch) in realized_pitches.iter().enumerate() {
+        if !pitches.contains(pitch) {
+            // Only the fifth of a chord is commonly left out.
+            cost += if i == 2 && numeral.named.is_none() {
+                1
+            } else {
+                4
+            };
+        }
+    }
+    for note in chord.notes.iter() {
+        let spelled = realized
+            .notes
+            .iter()
+            .find(|candidate| candidate.id().simple() == note.id().simple());
+        // Misspelling costs more than any gap between enharmonic candidates,
+        // so a chord spelled as an applied dominant is not read as an augmented sixth.
+        if spelled.is_some_and(|spelled| spelled.alphabet != note.alphabet) {
+            cost += 2;
+        }
+    }
+    let inversion = realized_pitches
+        .iter()
+        .position(|&pitch| pitch == bass.id().simple())?;
+    let mut numeral = numeral;
+    match numeral.named {
+        // Augmented sixths are only recognized with the sixth above the bass,
+        // and a cadential six-four is by definition in second inversion.
+        Some(
+            NamedChord::ItalianSixth
+            | NamedChord::FrenchSixth
+            | NamedChord::GermanSixth
+            | NamedChord::CadentialSixFour,
+        ) => {
+            if numeral.inversion.value_for(realized_pitches.len() as u8) as usize != inversion {
+                return None;
+            }
+        }
+        _ => numeral.inversion = Inversion::new(inversion as u8),
+    }
+    Some(Interpretation { numeral, cost })
+}
+
+/// Returns every root position numeral that the analysis considers, with the cost of choosing it.
+fn candidates(minor: bool) -> Vec<(RomanNumeral, u32)> {
+    let diatonic = |degree: u8| {
+        let mut numeral = match (minor, degree) {
+            (true, 5) => RomanNumeral::triad(5, Tonality::Major, Inversion::ROOT),
+            (true, 7) => RomanNumeral::triad(7, Tonality::Diminished, Inversion::ROOT),
+            (true, _) => RomanNumeral::minor_chord(degree, Inversion::ROOT),
+            (false, _) => RomanNumeral::major_chord(degree, Inversion::ROOT),
+        };
+        numeral.alteration = Accidental::None;
+        numeral
+    };
+    let with_seventh = |numeral: &RomanNumeral| {
+        let mut numeral = RomanNumeral {
+            additional_notes: RomanNumeral::seventh_chord(1, Tonality::Major, Inversion::ROOT)
+                .additional_notes,
+            ..numeral.clone()
+        };
+        // The diatonic sevenths on vii in major and ii in minor are half diminished.
+        let half_diminished = if minor { 2 } else { 7 };
+        if numeral.quality == Tonality::Diminished && numeral.degree == half_diminished {
+            numeral.quality = Tonality::HalfDiminished;
+        }
+        numeral
+    };
+
+    let mut candidates = vec![];
+    for degree in 1..=7 {
+        let numeral = diatonic(degree);
+        candidates.push((with_seventh(&numeral), 0));
+        candidates.push((numeral, 0));
+        if minor {
+            // The natural minor v and VII are diatonic too, just less common.
+            if degree == 5 || degree == 7 {
+                let natural = RomanNumeral::minor_chord(degree, Inversion::ROOT);
+                candidates.push((with_seventh(&natural), 1));
+                candidates.push((natural, 1));
+            }
+        } else {
+            let borrowed = RomanNumeral::borrowed(degree, Inversion::ROOT);
+            candidates.push((with_seventh(&borrowed), 2));
+            candidates.push((borrowed, 2));
+        }
+        for quality in [
+            Tonality::Major,
+            Tonality::Minor,
+            Tonality::Diminished,
+            Tonality::Augmented,
+            Tonality::HalfDiminished,
+        ] {
+            for alteration in [Accidental::None, Accidental::Flat, Accidental::Sharp] {
+                let numeral = RomanNumeral {
+                    alteration,
+                    ..RomanNumeral::triad(degree, quality, Inversion::ROOT)
+                };
+                let cost = if alteration == Accidental::None { 4 } else { 5 };
+                if quality != Tonality::HalfDiminished {
+                    candidates.push((numeral.clone(), cost));
+                }
+                candidates.push((with_seventh(&numeral), cost));
+            }
+        }
+    }
+    // The Neapolitan is tonicized as the major triad on the lowered second degree.
+    let neapolitan = RomanNumeral {
+        named: None,
+        ..RomanNumeral::neapolitan(Inversion::ROOT)
+    };
+    for target in (2..=7).map(diatonic).chain([neapolitan]) {
+        if target.quality == Tonality::Diminished {
+            continue;
+        }
+        let dominant = RomanNumeral::triad(5, Tonality::Major, Inversion::ROOT);
+        let leading = RomanNumeral::triad(7, Tonality::Diminished, Inversion::ROOT);
+        let half = RomanNumeral::triad(7, Tonality::HalfDiminished, Inversion::ROOT);
+        for numeral in [
+            with_seventh(&dominant),
+            dominant,
+            with_seventh(&leading),
+            leading,
+            with_seventh(&half),
+        ] {
+            candidates.push((numeral.with_secondary(target.clone()), 3));
+        }
+    }
+    candidates.push((RomanNumeral::neapolitan(Inversion::ROOT), 2));
+    for kind in [
+        NamedChord::ItalianSixth,
+        NamedChord::FrenchSixth,
+        NamedChord::GermanSixth,
+    ] {
+        candidates.extend(RomanNumeral::augmented_sixth(kind).map(|numeral| (numeral, 2)));
+    }
+    candidates.push((RomanNumeral::cadential_six_four(), 1));
+    candidates
+}
+
+/// The harmony sounding on one beat of a score.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeatAnalysis {
+    /// The measure, counting from 0.
+    pub measure: Measure,
+    /// The beat within the measure, counting from 0.
+    pub beat: Beat,
+    /// Every note sounding at the start of the beat, across all parts.
+    pub chord: Chord,
+    /// The key of the first part's measure.
+    pub key: Key,
+    /// Whether the beat is metrically strong.
+    pub strong: bool,
+    /// The possible roman numerals for the chord, best first.
+    pub interpretations: Vec<Interpretation>,
+}
+
+impl BeatAnalysis {
+    /// The best interpretation of the beat, if the chord could be analyzed.
+    pub fn numeral(&self) -> Option<&RomanNumeral> {
+        self.interpretations
+            .first()
+            .map(|interpretation| &interpretation.numeral)
+    }
+}
+
+/// Analyzes the harmony at the start of every beat of a score.
+///
+/// A cadential six-four is only chosen over a second inversion tonic when it falls on a strong beat,
+/// that is, the first beat of the measure or the middle beat of a measure with four or more beats.
+pub fn analyze_score(score: &Score) -> Vec<BeatAnalysis> {
+    let measures = score
+        .parts
+        .iter()
+        .map(|part| part.measures.len())
+        .max()
+        .unwrap_or(0);
+    let mut analysis = vec![];
+    for index in 0..measures {
+        let Some(first) = score.parts.iter().find_map(|part| part.measures.get(index)) else {
+            continue;
+        };
+        let time_signature = &first.time_signature;
+        for (beat, onset) in time_signature.beat_offsets().into_iter().enumerate() {
+            let notes = score
+                .parts
+                .iter()
+                .filter_map(|part| part.measures.get(index))
+                .flat_map(|measure| sounding(measure, onset))
+                .collect::<Vec<_>>();
+            let chord = Chord::new(notes);
+            let mut interpretations = analyze_chord(&chord, &first.key);
+            let strong = time_signature.metric_weight(onset).is_strong();
+            if !strong {
+                interpretations.retain(|i| i.numeral.named != Some(NamedChord::CadentialSixFour));
+            } else if let Some(position) = interpretations
+                .iter()
+                .position(|i| i.numeral.named == Some(NamedChord::CadentialSixFour))
+            {
+                let cadential = interpretations.remove(position);
+                interpretations.insert(0, cadential);
+            }
+            analysis.push(BeatAnalysis {
+                measure: Measure::new(index),
+                beat: Beat::new(beat),
+                chord,
+                key: first.key.clone(),
+                strong,
+                interpretations,
+            });
+        }
+    }
+    analysis
+}
+
+/// Labels the cadences in a progression, returning the index of each cadence's final chord.
+///
+/// Authentic, plagal and deceptive cadences are found wherever their chords follow each other,
+/// while half cadences are only found at the end of the progression.
+/// Without a soprano line, a V–I with both chords in root position is taken to be a perfect authentic cadence.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::analysis::{detect_cadences, Cadence};
+/// use note_pen::prelude::*;
+/// let progression = ["I", "IV", "V7", "I", "ii6", "V"]
+///     .map(|numeral| numeral.parse::<RomanNumeral>().unwrap());
+/// assert_eq!(
+///     detect_cadences(&progression),
+///     vec![(3, Cadence::PerfectAuthentic), (5, Cadence::Half)]
+/// );
+/// ```
+pub fn detect_cadences(numerals: &[RomanNumeral]) -> Vec<(usize, Cadence)> {
+    (1..numerals.len())
+        .filter_map(|i| {
+            cadence(&numerals[i - 1], &numerals[i], i == numerals.len() - 1).map(|c| (i, c))
+        })
+        .collect()
+}
+
+/// A cadence found in a score.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CadenceLocation {
+    pub cadence: Cadence,
+    /// The measure of the cadence's final chord.
+    pub measure: Measure,
+    /// The beat of the cadence's final chord.
+    pub beat: Beat,
+}
+
+/// Labels the cadences in a score using [`analyze_score`].
+///
+/// Repeated chords are merged, and a cadence's final chord must arrive on a strong beat.
+/// Phrases end before a beat where nothing sounds and at the end of the score.
+/// An authentic cadence is only perfect if the soprano ends on the tonic.
+pub fn find_cadences(score: &Score) -> Vec<CadenceLocation> {
+    let beats = analyze_score(score);
+    let mut harmonies: Vec<(&BeatAnalysis, bool)> = vec![];
+    for (i, beat) in beats.iter().enumerate() {
+        let phrase_end = beats
+            .get(i + 1)
+            .is_none_or(|next| next.chord.notes.is_empty());
+        match harmonies.last_mut() {
+            Some((previous, end)) if previous.numeral() == beat.numeral() => *end = phrase_end,
+            _ => harmonies.push((beat, phrase_end)),
+        }
+    }
+    harmonies
+        .windows(2)
+        .filter_map(|pair| {
+            let [(from, _), (to, phrase_end)] = pair else {
+                return None;
+            };
+            if !to.strong {
+                return None;
+            }
+            let mut cadence = cadence(from.numeral()?, to.numeral()?, *phrase_end)?;
+            let soprano = to.chord.notes.iter().max_by_key(|note| note.id());
+            let tonic = match &to.key {
+                Key::Diatonic { root, .. } => Some(root.id().simple()),
+                Key::Chromatic => None,
+            };
+            if cadence == Cadence::PerfectAuthentic
+                && soprano.map(|note| note.id().simple()) != tonic
+            {
+                cadence = Cadence::ImperfectAuthentic;
+            }
+            Some(CadenceLocation {
+                cadence,
+                measure: to.measure,
+                beat: to.beat,
+            })
+        })
+        .collect()
+}
+
+/// Classifies the motion between two chords, if it is a cadence.
+fn cadence(from: &RomanNumeral, to: &RomanNumeral, phrase_end: bool) -> Option<Cadence> {
+    let plain = |numeral: &RomanNumeral| {
+        numeral.secondary.is_none()
+            && numeral.named.is_none()
+            && numeral.alteration == Accidental::None
+    };
+    if !plain(from) || !plain(to) {
+        return None;
+    }
+    let root = |numeral: &RomanNumeral| numeral.inversion.value_for(3) == 0;
+    let dominant = from.degree == 5 && from.quality == Tonality::Major;
+    let leading = from.degree == 7
+        && matches!(
+            from.quality,
+            Tonality::Diminished | Tonality::HalfDiminished
+        );
+    let tonic = to.degree == 1 && matches!(to.quality, Tonality::Major | Tonality::Minor);
+    match (from.degree, to.degree) {
+        _ if dominant && tonic && root(from) && root(to) => Some(Cadence::PerfectAuthentic),
+        _ if (dominant || leading) && tonic => Some(Cadence::ImperfectAuthentic),
+        (5, 6) if dominant && !to.is_seventh() => Some(Cadence::Deceptive),
+        (4, 1) if tonic && !from.is_seventh() => Some(Cadence::Plagal),
+        (4, 5)
+            if phrase_end
+                && from.quality == Tonality::Minor
+                && from.inversion.value_for(3) == 1
+                && to.quality == Tonality::Major
+                && !to.is_seventh() =>
+        {
+            Some(Cadence::PhrygianHalf)
+        }
+        (_, 5) if phrase_end && to.quality == Tonality::Major && !to.is_seventh() => {
+            Some(Cadence::Half)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the notes of every voice sounding at `onset` into the measure.
+fn sounding(measure: &crate::measure::Measure, onset: Fraction) -> Vec<Note> {
+    let mut notes = vec![];
+    for voice in measure.voices() {
+        for item in voice.items {
+            if item.onset <= onset && onset < item.end() {
+                match item.item.item() {
+                    TimedMeasureItemInner::Note(note) => notes.push(*note),
+                    TimedMeasureItemInner::Chord(chord) => {
+                        notes.extend(chord.notes.iter().copied())
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duration::Duration;
+    use crate::measure::{Measure as ScoreMeasure, TimedMeasureItem};
+    use crate::{Alphabet, Clef, Part, TimeSignature};
+
+    fn chord(notes: &[(Alphabet, Accidental, u8)]) -> Chord {
+        Chord::new(
+            notes
+                .iter()
+                .map(|&(alphabet, accidental, octave)| Note::new(alphabet, accidental, octave))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_diatonic() {
+        let key = Key::new_major(Note::new(Alphabet::G, Accidental::Natural, 4)).unwrap();
+        let cases = [
+            (
+                chord(&[
+                    (Alphabet::G, Accidental::Natural, 4),
+                    (Alphabet::B, Accidental::Natural, 5),
+                    (Alphabet::D, Accidental::Natural, 5),
+                ]),
+                "I",
+            ),
+            (
+                chord(&[
+                    (Alphabet::A, Accidental::Natural, 4),
+                    (Alphabet::C, Accidental::Natural, 4),
+                    (Alphabet::E, Accidental::Natural, 4),
+                    (Alphabet::G, Accidental::Natural, 4),
+                ]),
+                "ii7",
+            ),
+            (
+                chord(&[
+                    (Alphabet::F, Accidental::Sharp, 4),
+                    (Alphabet::D, Accidental::Natural, 5),
+                    (Alphabet::A, Accidental::Natural, 5),
+                ]),
+                "V6",
+            ),
+            (
+                chord(&[
+                    (Alphabet::G, Accidental::Natural, 4),
+                    (Alphabet::B, Accidental::Natural, 5),
+                    (Alphabet::G, Accidental::Natural, 5),
+                ]),
+                "I",
+            ),
+        ];
+        for (chord, expected) in cases {
+            let interpretations = analyze_chord(&chord, &key);
+            assert_eq!(interpretations[0].numeral.to_string(), expected);
+        }
+        // The seventh chord on ii in minor is half diminished.
+        let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        let supertonic = chord(&[
+            (Alphabet::B, Accidental::Natural, 4),
+            (Alphabet::D, Accidental::Natural, 5),
+            (Alphabet::F, Accidental::Natural, 5),
+            (Alphabet::A, Accidental::Natural, 5),
+        ]);
+        let interpretations = analyze_chord(&supertonic, &key);
+        assert_eq!(interpretations[0].numeral.to_string(), "iiø7");
+        assert_eq!(interpretations[0].cost, 0);
+    }
+
+    #[test]
+    fn test_chromatic() {
+        let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        let german = chord(&[
+            (Alphabet::F, Accidental::Natural, 4),
+            (Alphabet::A, Accidental::Natural, 5),
+            (Alphabet::C, Accidental::Natural, 5),
+            (Alphabet::D, Accidental::Sharp, 5),
+        ]);
+        let german = analyze_chord(&german, &key);
+        assert_eq!(german[0].numeral.to_string(), "Ger+6");
+        assert_eq!(german[1].numeral.to_string(), "V7/bII");
+        // The same pitches spelled as the dominant seventh of the Neapolitan.
+        let dominant = chord(&[
+            (Alphabet::F, Accidental::Natural, 4),
+            (Alphabet::A, Accidental::Natural, 5),
+            (Alphabet::C, Accidental::Natural, 5),
+            (Alphabet::E, Accidental::Flat, 5),
+        ]);
+        let dominant = analyze_chord(&dominant, &key);
+        assert_eq!(dominant[0].numeral.to_string(), "V7/bII");
+        assert_eq!(dominant[1].numeral.to_string(), "Ger+6");
+
+        let neapolitan = chord(&[
+            (Alphabet::D, Accidental::Natural, 4),
+            (Alphabet::F, Accidental::Natural, 4),
+            (Alphabet::B, Accidental::Flat, 5),
+        ]);
+        assert_eq!(
+            analyze_chord(&neapolitan, &key)[0].numeral.to_string(),
+            "N6"
+        );
+    }
+
+    #[test]
+    fn test_detect_cadences() {
+        let progression = |numerals: &[&str]| {
+            numerals
+                .iter()
+                .map(|numeral| numeral.parse::<RomanNumeral>().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let cases = [
+            (&["I", "V6", "I"][..], Cadence::ImperfectAuthentic),
+            (&["I", "viio6", "I6"][..], Cadence::ImperfectAuthentic),
+            (&["I", "V7", "vi"][..], Cadence::Deceptive),
+            (&["I", "IV", "I"][..], Cadence::Plagal),
+            (&["i", "iv6", "V"][..], Cadence::PhrygianHalf),
+            (&["I", "ii", "V"][..], Cadence::Half),
+        ];
+        for (numerals, expected) in cases {
+            assert_eq!(detect_cadences(&progression(numerals)), vec![(2, expected)]);
+        }
+        // A half cadence needs to end the phrase.
+        assert!(detect_cadences(&progression(&["I", "V", "IV"])).is_empty());
+    }
+
+    #[test]
+    fn test_score() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let measure = |chord: Chord| ScoreMeasure {
+            notes: vec![TimedMeasureItem::chord(chord, Duration::HALF)],
+            ..ScoreMeasure::new(Clef::Treble, TimeSignature::new(2, 4), key.clone())
+        };
+        let part = Part {
+            measures: vec![
+                measure(RomanNumeral::major_chord(1, Inversion::ROOT).chord(&key)),
+                measure(RomanNumeral::cadential_six_four().chord(&key)),
+            ],
+            ..Default::default()
+        };
+        let score = Score {
+            parts: vec![part],
+            ..Default::default()
+        };
+        let analysis = analyze_score(&score);
+        assert_eq!(analysis.len(), 4);
+        assert_eq!(analysis[1].numeral().unwrap().to_string(), "I");
+        assert_eq!(analysis[2].measure.value(), 1);
+        assert_eq!(analysis[2].numeral().unwrap().to_string(), "Cad64");
+        assert_eq!(analysis[3].numeral().unwrap().to_string(), "I64");
+    }
+
+    #[test]
+    fn test_find_cadences() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let measure = |chord: Chord| ScoreMeasure {
+            notes: vec![TimedMeasureItem::chord(chord, Duration::WHOLE)],
+            ..ScoreMeasure::new(Clef::Treble, TimeSignature::new(4, 4), key.clone())
+        };
+        let c = |alphabet| Note::new(alphabet, Accidental::Natural, 4);
+        let high = |alphabet| Note::new(alphabet, Accidental::Natural, 5);
+        let part = Part {
+            measures: vec![
+                measure(c(Alphabet::C) + c(Alphabet::E) + high(Alphabet::G)),
+                measure(c(Alphabet::G) + high(Alphabet::B) + high(Alphabet::D)),
+                measure(c(Alphabet::C) + c(Alphabet::E) + high(Alphabet::C)),
+            ],
+            ..Default::default()
+        };
+        let score = Score {
+            parts: vec![part],
+            ..Default::default()
+        };
+        let cadences = find_cadences(&score);
+        assert_eq!(cadences.len(), 1);
+        assert_eq!(cadences[0].cadence, Cadence::PerfectAuthentic);
+        assert_eq!(cadences[0].measure.value(), 2);
+        assert_eq!(cadences[0].beat.value(), 0);
+    }
+}
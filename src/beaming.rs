@@ -0,0 +1,266 @@
+//! Automatic beaming of eighth notes and shorter.
+
+use crate::duration::Fraction;
+use crate::measure::{Beam, Measure, TimedMeasureItem, TimedMeasureItemInner};
+use crate::TimeSignature;
+
+/// How the items of a measure are grouped under beams.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeamGrouping {
+    /// The lengths of the groups that beams do not cross, repeating through the measure.
+    pub groups: Vec<Fraction>,
+    /// The length at which the beams after the first are broken within a group, if any.
+    pub secondary: Option<Fraction>,
+}
+
+impl BeamGrouping {
+    /// Beams each beat of the time signature together.
+    /// In compound time, the beams after the first are broken at each third of the beat,
+    /// so six sixteenths in a dotted quarter are beamed in three pairs under one eighth beam.
+    /// When the beats are eighths or shorter, as in 3/8, the whole measure is beamed together,
+    /// with the beams after the first broken at each beat.
+    pub fn from_time_signature(time_signature: &TimeSignature) -> Self {
+        let beats = time_signature.beat_lengths();
+        let short = Fraction::new(1, 8);
+        if !beats.is_empty() && beats.iter().all(|&beat| beat <= short) {
+            return Self {
+                groups: vec![time_signature.measure_length()],
+                secondary: Some(time_signature.beat_length()),
+            };
+        }
+        let secondary = if time_signature.is_compound() {
+            Some(time_signature.beat_length() * Fraction::new(1, 3))
+        } else {
+            None
+        };
+        Self {
+            groups: beats,
+            secondary,
+        }
+    }
+
+    /// The index and start of the group that `onset` falls in.
+    fn group_at(&self, onset: Fraction) -> (usize, Fraction) {
+        if self.groups.iter().all(Fraction::is_zero) {
+            return (0, Fraction::ZERO);
+        }
+        let mut start = Fraction::ZERO;
+        for (index, length) in self.groups.iter().cycle().enumerate() {
+            if onset < start + *length {
+                return (index, start);
+            }
+            start = start + *length;
+        }
+        unreachable!("the groups repeat forever")
+    }
+}
+
+/// The number of beams an item has, which is 0 for items that cannot be beamed.
+fn levels(item: &TimedMeasureItem) -> usize {
+    let length = item.duration.primitive.length();
+    match item.item {
+        TimedMeasureItemInner::Note(_)
+        | TimedMeasureItemInner::Chord(_)
+        | TimedMeasureItemInner::Rest
+            if length.numerator() == 1 && length.denominator() >= 8 =>
+        {
+            length.denominator().trailing_zeros() as usize - 2
+        }
+        _ => 0,
+    }
+}
+
+/// Whether `length` is a whole number of `unit`s.
+fn is_multiple(length: Fraction, unit: Fraction) -> bool {
+    (length * Fraction::new(unit.denominator(), unit.numerator())).denominator() == 1
+}
+
+/// Beams the items of a measure by the beats of its time signature.
+pub fn beam_measure(measure: &mut Measure) {
+    let grouping = BeamGrouping::from_time_signature(&measure.time_signature);
+    beam(&mut measure.notes, &grouping);
+}
+
+/// Beams eighth notes and shorter, replacing any beams the items already have.
+///
+/// Each voice is beamed on its own, and beams never cross a group of the grouping.
+/// Rests between beamed notes are placed under the beam,
+/// but a beam never starts or ends with a rest.
+/// Every beam is given its own [`TimedMeasureItem::beam_id`], counting from 1.
+pub fn beam(items: &mut [TimedMeasureItem], grouping: &BeamGrouping) {
+    for item in items.iter_mut() {
+        item.beam_id = None;
+        item.beams.clear();
+    }
+    let mut voices = vec![];
+    for item in items.iter() {
+        let voice = item.voice.unwrap_or(1);
+        if !voices.contains(&voice) {
+            voices.push(voice);
+        }
+    }
+    let mut next_id = 1;
+    for voice in voices {
+        let mut onset = Fraction::ZERO;
+        // The items that may share a beam, with their onsets, and the group they are in.
+        let mut run = vec![];
+        let mut run_group = None;
+        for index in 0..items.len() {
+            let item = &items[index];
+            if item.voice.unwrap_or(1) != voice || item.item == TimedMeasureItemInner::Backward {
+                continue;
+            }
+            let group = grouping.group_at(onset);
+            let beamable = levels(item) > 0;
+            if !beamable || run_group != Some(group) {
+                beam_run(items, &run, grouping, &mut next_id);
+                run.clear();
+                run_group = None;
+            }
+            if beamable {
+                run.push((index, onset));
+                run_group = Some(group);
+            }
+            onset = onset + items[index].duration.length();
+        }
+        beam_run(items, &run, grouping, &mut next_id);
+    }
+}
+
+/// Beams a run of beamable items in the same group, given with their onsets.
+fn beam_run(
+    items: &mut [TimedMeasureItem],
+    run: &[(usize, Fraction)],
+    grouping: &BeamGrouping,
+    next_id: &mut u16,
+) {
+    let is_rest =
+        |&(index, _): &(usize, Fraction)| items[index].item == TimedMeasureItemInner::Rest;
+    let start = run.iter().position(|entry| !is_rest(entry));
+    let end = run.iter().rposition(|entry| !is_rest(entry));
+    let (Some(start), Some(end)) = (start, end) else {
+        return;
+    };
+    let run = &run[start..=end];
+    if run.len() < 2 {
+        return;
+    }
+    let (_, group_start) = grouping.group_at(run[0].1);
+    let levels = run
+        .iter()
+        .map(|&(index, _)| levels(&items[index]))
+        .collect::<Vec<_>>();
+    // Whether the beams after the first continue from item i - 1 to item i.
+    let joined = |i: usize| {
+        i > 0
+            && i < run.len()
+            && !grouping
+                .secondary
+                .is_some_and(|secondary| is_multiple(run[i].1 - group_start, secondary))
+    };
+    for (i, &(index, onset)) in run.iter().enumerate() {
+        let mut beams = vec![];
+        for level in 1..=levels[i] {
+            let previous = i > 0 && levels[i - 1] >= level && (level == 1 || joined(i));
+            let next = i + 1 < run.len() && levels[i + 1] >= level && (level == 1 || joined(i + 1));
+            beams.push(match (previous, next) {
+                (true, true) => Beam::Continue,
+                (true, false) => Beam::End,
+                (false, true) => Beam::Begin,
+                (false, false) => {
+                    // A lone short note hooks towards the note it shares its pulse with.
+                    let pulse = items[index].duration.length() * 2;
+                    if i == 0 || (i + 1 < run.len() && is_multiple(onset - group_start, pulse)) {
+                        Beam::ForwardHook
+                    } else {
+                        Beam::BackwardHook
+                    }
+                }
+            });
+        }
+        items[index].beam_id = Some(*next_id);
+        items[index].beams = beams;
+    }
+    *next_id += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn note(duration: Duration) -> TimedMeasureItem {
+        TimedMeasureItem::note(Note::new(Alphabet::C, Accidental::Natural, 4), duration)
+    }
+
+    fn rest(duration: Duration) -> TimedMeasureItem {
+        TimedMeasureItem::rest(duration)
+    }
+
+    #[test]
+    fn test_beam_by_beat() {
+        let mut items = vec![
+            note(Duration::EIGHTH),
+            note(Duration::EIGHTH),
+            note(Duration::new(PrimitiveDuration::EIGHTH, 1)),
+            note(Duration::SIXTEENTH),
+            note(Duration::QUARTER),
+            rest(Duration::EIGHTH),
+            note(Duration::EIGHTH),
+        ];
+        let grouping = BeamGrouping::from_time_signature(&TimeSignature::COMMON_TIME);
+        beam(&mut items, &grouping);
+        let ids = items.iter().map(|item| item.beam_id()).collect::<Vec<_>>();
+        assert_eq!(ids, [Some(1), Some(1), Some(2), Some(2), None, None, None]);
+        assert_eq!(items[2].beams(), [Beam::Begin]);
+        assert_eq!(items[3].beams(), [Beam::End, Beam::BackwardHook]);
+    }
+
+    #[test]
+    fn test_beam_compound() {
+        let mut items = vec![
+            note(Duration::SIXTEENTH),
+            note(Duration::SIXTEENTH),
+            rest(Duration::EIGHTH),
+            note(Duration::SIXTEENTH),
+            note(Duration::SIXTEENTH),
+        ];
+        let grouping = BeamGrouping::from_time_signature(&TimeSignature::new(6, 8));
+        beam(&mut items, &grouping);
+        assert!(items.iter().all(|item| item.beam_id() == Some(1)));
+        assert_eq!(items[1].beams(), [Beam::Continue, Beam::End]);
+        assert_eq!(items[2].beams(), [Beam::Continue]);
+        assert_eq!(items[3].beams(), [Beam::Continue, Beam::Begin]);
+        // Voices are beamed separately.
+        let mut items = vec![note(Duration::EIGHTH), note(Duration::EIGHTH).with_voice(2)];
+        beam(&mut items, &grouping);
+        assert!(items.iter().all(|item| item.beam_id().is_none()));
+    }
+
+    #[test]
+    fn test_beam_short_beats() {
+        // The beats of 3/8 are eighths, so the whole measure is one group.
+        let grouping = BeamGrouping::from_time_signature(&TimeSignature::new(3, 8));
+        let mut items = vec![note(Duration::EIGHTH); 3];
+        beam(&mut items, &grouping);
+        let ids = items.iter().map(|item| item.beam_id()).collect::<Vec<_>>();
+        assert_eq!(ids, [Some(1), Some(1), Some(1)]);
+        let mut items = vec![note(Duration::SIXTEENTH); 6];
+        beam(&mut items, &grouping);
+        assert!(items.iter().all(|item| item.beam_id() == Some(1)));
+        assert_eq!(items[1].beams(), [Beam::Continue, Beam::End]);
+        assert_eq!(items[2].beams(), [Beam::Continue, Beam::Begin]);
+        let grouping = BeamGrouping::from_time_signature(&TimeSignature::new(2, 8));
+        let mut items = vec![note(Duration::EIGHTH); 2];
+        beam(&mut items, &grouping);
+        assert_eq!(items[0].beams(), [Beam::Begin]);
+        assert_eq!(items[1].beams(), [Beam::End]);
+        // In 2/4, the eighths are beamed in pairs, one for each beat.
+        let grouping = BeamGrouping::from_time_signature(&TimeSignature::new(2, 4));
+        let mut items = vec![note(Duration::EIGHTH); 4];
+        beam(&mut items, &grouping);
+        let ids = items.iter().map(|item| item.beam_id()).collect::<Vec<_>>();
+        assert_eq!(ids, [Some(1), Some(1), Some(2), Some(2)]);
+    }
+}
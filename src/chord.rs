@@ -11,7 +11,7 @@ pub struct Chord {
 
 /// An inversion is a way to rearrange the notes of a chord so that a different note is the lowest note.
 /// Root inversion is stored as 0.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct Inversion(u8);
@@ -93,7 +93,7 @@ impl Chord {
                 notes.push(root + Interval::MINOR_THIRD);
                 notes.push(root + Interval::PERFECT_FIFTH);
             }
-            Tonality::Diminished => {
+            Tonality::Diminished | Tonality::HalfDiminished => {
                 notes.push(root + Interval::MINOR_THIRD);
                 notes.push(root + Interval::TRITONE);
             }
@@ -123,7 +123,7 @@ impl Chord {
                 let int = match tonality {
                     Tonality::Major => Interval::MAJOR_THIRD,
                     Tonality::Minor => Interval::MINOR_THIRD,
-                    Tonality::Diminished => Interval::MINOR_THIRD,
+                    Tonality::Diminished | Tonality::HalfDiminished => Interval::MINOR_THIRD,
                     Tonality::Augmented => Interval::MAJOR_THIRD,
                 };
                 let root = base - int;
@@ -133,7 +133,7 @@ impl Chord {
                 let int = match tonality {
                     Tonality::Major => Interval::PERFECT_FIFTH,
                     Tonality::Minor => Interval::PERFECT_FIFTH,
-                    Tonality::Diminished => Interval::TRITONE,
+                    Tonality::Diminished | Tonality::HalfDiminished => Interval::TRITONE,
                     Tonality::Augmented => Interval::AUGMENTED_FIFTH,
                 };
                 let root = base - int;
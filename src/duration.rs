@@ -1,59 +1,185 @@
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+
+/// A note value without dots, from a longa (four whole notes) to a 256th note.
+///
+/// Internally, it stores the power of two that divides a whole note, which is negative for notes longer than a whole note.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct PrimitiveDuration(u64);
+pub struct PrimitiveDuration(i8);
+
 impl TryFrom<u64> for PrimitiveDuration {
     type Error = ();
 
+    /// Creates the note value that divides a whole note into `value` parts, like 4 for a quarter note.
     fn try_from(value: u64) -> Result<Self, Self::Error> {
-        match value {
-            1 => Ok(Self::WHOLE),
-            2 => Ok(Self::HALF),
-            4 => Ok(Self::QUARTER),
-            8 => Ok(Self::EIGHTH),
-            16 => Ok(Self::SIXTEENTH),
-            32 => Ok(Self::THIRTY_SECOND),
-            64 => Ok(Self::SIXTY_FOURTH),
+        if !value.is_power_of_two() {
+            return Err(());
+        }
+        let duration = Self(value.trailing_zeros() as i8);
+        if duration.0 > Self::SHORTEST.0 {
+            return Err(());
+        }
+        Ok(duration)
+    }
+}
+
+impl TryFrom<Fraction> for PrimitiveDuration {
+    type Error = ();
+
+    fn try_from(length: Fraction) -> Result<Self, Self::Error> {
+        match (length.numerator(), length.denominator()) {
+            (1, denominator) => Self::try_from(denominator),
+            (2, 1) => Ok(Self::BREVE),
+            (4, 1) => Ok(Self::LONGA),
             _ => Err(()),
         }
     }
 }
 
 impl PrimitiveDuration {
-    pub const WHOLE: Self = Self(1);
-    pub const HALF: Self = Self(2);
-    pub const QUARTER: Self = Self(4);
-    pub const EIGHTH: Self = Self(8);
-    pub const SIXTEENTH: Self = Self(16);
-    pub const THIRTY_SECOND: Self = Self(32);
-    pub const SIXTY_FOURTH: Self = Self(64);
+    pub const LONGA: Self = Self(-2);
+    pub const BREVE: Self = Self(-1);
+    pub const WHOLE: Self = Self(0);
+    pub const HALF: Self = Self(1);
+    pub const QUARTER: Self = Self(2);
+    pub const EIGHTH: Self = Self(3);
+    pub const SIXTEENTH: Self = Self(4);
+    pub const THIRTY_SECOND: Self = Self(5);
+    pub const SIXTY_FOURTH: Self = Self(6);
+    pub const HUNDRED_TWENTY_EIGHTH: Self = Self(7);
+    pub const TWO_HUNDRED_FIFTY_SIXTH: Self = Self(8);
+
+    /// The longest note value.
+    pub const LONGEST: Self = Self::LONGA;
+    /// The shortest note value.
+    pub const SHORTEST: Self = Self::TWO_HUNDRED_FIFTY_SIXTH;
 
+    /// The number of notes of this value in a whole note, like 4 for a quarter note.
+    ///
+    /// Returns `None` for breves and longas, which are longer than a whole note;
+    /// use [`PrimitiveDuration::length`] for those.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// assert_eq!(PrimitiveDuration::QUARTER.value(), Some(4));
+    /// assert_eq!(PrimitiveDuration::BREVE.value(), None);
+    /// ```
     #[inline]
-    pub const fn value(&self) -> u64 {
-        self.0
+    pub const fn value(&self) -> Option<u64> {
+        if self.0 < 0 {
+            None
+        } else {
+            Some(1 << self.0)
+        }
+    }
+
+    /// The exact length of the note value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// assert_eq!(PrimitiveDuration::BREVE.length(), Fraction::new(2, 1));
+    /// assert_eq!(PrimitiveDuration::QUARTER.length(), Fraction::new(1, 4));
+    /// ```
+    #[inline]
+    pub const fn length(&self) -> Fraction {
+        if self.0 < 0 {
+            Fraction::new(1 << -self.0, 1)
+        } else {
+            Fraction::new(1, 1 << self.0)
+        }
     }
 
     /// Halves the duration of the primitive duration.
     /// This is equivalent to halving the note value.
+    /// The shortest note value is returned unchanged.
     /// # Examples
     /// ```rust
     /// use note_pen::prelude::*;
     /// assert_eq!(PrimitiveDuration::WHOLE.half(), PrimitiveDuration::HALF);
+    /// assert_eq!(PrimitiveDuration::BREVE.half(), PrimitiveDuration::WHOLE);
     /// ```
     #[inline]
     pub const fn half(&self) -> Self {
-        Self(self.0 * 2)
+        if self.0 < Self::SHORTEST.0 {
+            Self(self.0 + 1)
+        } else {
+            *self
+        }
     }
 
     /// Doubles the duration of the primitive duration.
     /// This is equivalent to doubling the note value.
+    /// The longest note value is returned unchanged.
     /// # Examples
     /// ```rust
     /// use note_pen::prelude::*;
     /// assert_eq!(PrimitiveDuration::HALF.double(), PrimitiveDuration::WHOLE);
+    /// assert_eq!(PrimitiveDuration::BREVE.double(), PrimitiveDuration::LONGA);
     /// ```
     #[inline]
     pub const fn double(&self) -> Self {
-        Self(self.0 / 2)
+        if self.0 > Self::LONGEST.0 {
+            Self(self.0 - 1)
+        } else {
+            *self
+        }
+    }
+}
+
+/// Plays `actual_notes` notes in the time of `normal_notes`, like 3 eighths in the time of 2 for a triplet.
+///
+/// Nested tuplets are represented by the product of their ratios, so a triplet inside a quintuplet is 15:8,
+/// matching MusicXML's `<time-modification>`.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::duration::{Fraction, Tuplet};
+/// let nested = Tuplet::QUINTUPLET.nest(Tuplet::TRIPLET).unwrap();
+/// assert_eq!(nested, Tuplet::new(15, 8));
+/// assert_eq!(nested.ratio(), Fraction::new(8, 15));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuplet {
+    pub actual_notes: u16,
+    pub normal_notes: u16,
+}
+
+impl Tuplet {
+    pub const DUPLET: Self = Self::new(2, 3);
+    pub const TRIPLET: Self = Self::new(3, 2);
+    pub const QUINTUPLET: Self = Self::new(5, 4);
+    pub const SEXTUPLET: Self = Self::new(6, 4);
+    pub const SEPTUPLET: Self = Self::new(7, 4);
+
+    pub const fn new(actual_notes: u16, normal_notes: u16) -> Self {
+        Self {
+            actual_notes,
+            normal_notes,
+        }
+    }
+
+    /// The factor the tuplet scales each note by.
+    pub const fn ratio(&self) -> Fraction {
+        Fraction::new(self.normal_notes as u64, self.actual_notes as u64)
+    }
+
+    /// Places the `inner` tuplet inside this one.
+    /// Returns `None` if the nested ratio is too large to store.
+    pub const fn nest(&self, inner: Self) -> Option<Self> {
+        match (
+            self.actual_notes.checked_mul(inner.actual_notes),
+            self.normal_notes.checked_mul(inner.normal_notes),
+        ) {
+            (Some(actual_notes), Some(normal_notes)) => Some(Self::new(actual_notes, normal_notes)),
+            _ => None,
+        }
     }
 }
 
@@ -61,44 +187,388 @@ impl PrimitiveDuration {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Duration {
     pub primitive: PrimitiveDuration,
+    /// The number of dots, at most [`Duration::MAX_DOTS`].
     pub dots: u8,
+    /// The tuplet the note is played in, if any.
+    pub tuplet: Option<Tuplet>,
 }
 
 impl From<PrimitiveDuration> for Duration {
     fn from(primitive: PrimitiveDuration) -> Self {
-        Self { primitive, dots: 0 }
+        Self::new(primitive, 0)
     }
 }
 
 impl Duration {
-    pub const WHOLE: Self = Self {
-        primitive: PrimitiveDuration::WHOLE,
-        dots: 0,
-    };
-    pub const HALF: Self = Self {
-        primitive: PrimitiveDuration::HALF,
-        dots: 0,
-    };
-    pub const QUARTER: Self = Self {
-        primitive: PrimitiveDuration::QUARTER,
-        dots: 0,
-    };
-    pub const EIGHTH: Self = Self {
-        primitive: PrimitiveDuration::EIGHTH,
-        dots: 0,
-    };
-    pub const SIXTEENTH: Self = Self {
-        primitive: PrimitiveDuration::SIXTEENTH,
-        dots: 0,
-    };
-    pub const THIRTY_SECOND: Self = Self {
-        primitive: PrimitiveDuration::THIRTY_SECOND,
-        dots: 0,
+    pub const LONGA: Self = Self::new(PrimitiveDuration::LONGA, 0);
+    pub const BREVE: Self = Self::new(PrimitiveDuration::BREVE, 0);
+    pub const WHOLE: Self = Self::new(PrimitiveDuration::WHOLE, 0);
+    pub const HALF: Self = Self::new(PrimitiveDuration::HALF, 0);
+    pub const QUARTER: Self = Self::new(PrimitiveDuration::QUARTER, 0);
+    pub const EIGHTH: Self = Self::new(PrimitiveDuration::EIGHTH, 0);
+    pub const SIXTEENTH: Self = Self::new(PrimitiveDuration::SIXTEENTH, 0);
+    pub const THIRTY_SECOND: Self = Self::new(PrimitiveDuration::THIRTY_SECOND, 0);
+    pub const SIXTY_FOURTH: Self = Self::new(PrimitiveDuration::SIXTY_FOURTH, 0);
+    pub const HUNDRED_TWENTY_EIGHTH: Self = Self::new(PrimitiveDuration::HUNDRED_TWENTY_EIGHTH, 0);
+    pub const TWO_HUNDRED_FIFTY_SIXTH: Self =
+        Self::new(PrimitiveDuration::TWO_HUNDRED_FIFTY_SIXTH, 0);
+}
+
+impl Duration {
+    /// The most dots a duration can have while its length still fits in a [`Fraction`].
+    pub const MAX_DOTS: u8 = 16;
+
+    /// Creates a duration that is not in a tuplet.
+    ///
+    /// # Panics
+    /// Panics if `dots` is greater than [`Duration::MAX_DOTS`].
+    pub const fn new(primitive: PrimitiveDuration, dots: u8) -> Self {
+        assert!(
+            dots <= Self::MAX_DOTS,
+            "a duration cannot have more than Duration::MAX_DOTS dots"
+        );
+        Self {
+            primitive,
+            dots,
+            tuplet: None,
+        }
+    }
+
+    /// Places the duration in a tuplet, nesting it inside any tuplet it is already in.
+    /// Returns `None` if the nested tuplet is too large to store, see [`Tuplet::nest`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::{Fraction, Tuplet};
+    /// let triplet = Duration::EIGHTH.with_tuplet(Tuplet::TRIPLET).unwrap();
+    /// assert_eq!(triplet.length() * 3, Duration::QUARTER.length());
+    /// ```
+    pub const fn with_tuplet(self, tuplet: Tuplet) -> Option<Self> {
+        let tuplet = match self.tuplet {
+            Some(outer) => match outer.nest(tuplet) {
+                Some(nested) => nested,
+                None => return None,
+            },
+            None => tuplet,
+        };
+        Some(Self {
+            tuplet: Some(tuplet),
+            ..self
+        })
+    }
+
+    /// The exact length of the duration, including dots and tuplets.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let double_dotted = Duration::new(PrimitiveDuration::QUARTER, 2);
+    /// assert_eq!(double_dotted.length(), Fraction::new(7, 16));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if [`Duration::dots`] is greater than [`Duration::MAX_DOTS`].
+    pub const fn length(&self) -> Fraction {
+        assert!(
+            self.dots <= Self::MAX_DOTS,
+            "a duration cannot have more than Duration::MAX_DOTS dots"
+        );
+        let dots = 1u64 << self.dots;
+        let (actual, normal) = match self.tuplet {
+            Some(tuplet) => (tuplet.actual_notes as u64, tuplet.normal_notes as u64),
+            None => (1, 1),
+        };
+        let primitive = self.primitive.length();
+        Fraction::new(
+            primitive.numerator() * (2 * dots - 1) * normal,
+            primitive.denominator() * dots * actual,
+        )
+    }
+}
+
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Duration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |duration: &Self| {
+            let tuplet = duration
+                .tuplet
+                .map(|tuplet| (tuplet.actual_notes, tuplet.normal_notes));
+            (duration.length(), duration.dots, tuplet)
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+impl Add for Duration {
+    type Output = Fraction;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.length() + rhs.length()
+    }
+}
+
+impl From<Duration> for Fraction {
+    fn from(duration: Duration) -> Self {
+        duration.length()
+    }
+}
+
+impl TryFrom<Fraction> for Duration {
+    type Error = ();
+
+    /// Finds the notated duration with the given length, with any number of dots and no tuplet.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let dotted_half = Duration::try_from(Fraction::new(3, 4)).unwrap();
+    /// assert_eq!(dotted_half, Duration::new(PrimitiveDuration::HALF, 1));
+    /// assert!(Duration::try_from(Fraction::new(5, 8)).is_err());
+    /// let dotted_breve = Duration::try_from(Fraction::new(3, 1)).unwrap();
+    /// assert_eq!(dotted_breve, Duration::new(PrimitiveDuration::BREVE, 1));
+    /// ```
+    fn try_from(length: Fraction) -> Result<Self, Self::Error> {
+        // A duration with n dots is the primitive length times (2^(n+1) - 1) / 2^n,
+        // so the odd part of the numerator is one less than a power of two.
+        if length.is_zero() {
+            return Err(());
+        }
+        let odd = length.numerator() >> length.numerator().trailing_zeros();
+        let dots = (odd + 1).trailing_zeros() - 1;
+        if !(odd + 1).is_power_of_two() {
+            return Err(());
+        }
+        let primitive = length * Fraction::new(1 << dots, odd);
+        Ok(Self::new(
+            PrimitiveDuration::try_from(primitive)?,
+            dots as u8,
+        ))
+    }
+}
+
+/// An exact length of time, as a fraction of a whole note.
+///
+/// Fractions are always kept in lowest terms, so equal lengths compare equal.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::duration::Fraction;
+/// let measure = [Duration::HALF, Duration::QUARTER, Duration::EIGHTH, Duration::EIGHTH]
+///     .into_iter()
+///     .map(Fraction::from)
+///     .sum::<Fraction>();
+/// assert_eq!(measure, Fraction::ONE);
+/// assert_eq!(Duration::QUARTER.length().ticks(480), Some(480));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fraction {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl Fraction {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
     };
-    pub const SIXTY_FOURTH: Self = Self {
-        primitive: PrimitiveDuration::SIXTY_FOURTH,
-        dots: 0,
+    /// The length of a whole note.
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
     };
+
+    /// Creates a fraction in lowest terms.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0.
+    pub const fn new(numerator: u64, denominator: u64) -> Self {
+        assert!(
+            denominator != 0,
+            "the denominator of a fraction cannot be 0"
+        );
+        let divisor = gcd(numerator, denominator);
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    #[inline]
+    pub const fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    #[inline]
+    pub const fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    #[inline]
+    pub const fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The fraction as a floating point number, for computations that cannot be exact.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Subtracts `rhs`, returning `None` if the result would be negative.
+    pub const fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        let left = self.numerator as u128 * rhs.denominator as u128;
+        let right = rhs.numerator as u128 * self.denominator as u128;
+        if left < right {
+            return None;
+        }
+        Some(Self::from_wide(
+            left - right,
+            self.denominator as u128 * rhs.denominator as u128,
+        ))
+    }
+
+    /// Converts the length to ticks, given the number of ticks in a quarter note as in MIDI,
+    /// returning `None` if the length is not a whole number of ticks.
+    pub const fn ticks(&self, ticks_per_quarter: u64) -> Option<u64> {
+        let ticks = self.numerator as u128 * 4 * ticks_per_quarter as u128;
+        if !ticks.is_multiple_of(self.denominator as u128) {
+            return None;
+        }
+        let ticks = ticks / self.denominator as u128;
+        if ticks > u64::MAX as u128 {
+            return None;
+        }
+        Some(ticks as u64)
+    }
+
+    /// Creates a length from a number of ticks, given the number of ticks in a quarter note.
+    pub const fn from_ticks(ticks: u64, ticks_per_quarter: u64) -> Self {
+        Self::from_wide(ticks as u128, 4 * ticks_per_quarter as u128)
+    }
+
+    /// Reduces a fraction computed with wider integers.
+    ///
+    /// # Panics
+    /// Panics if the reduced fraction does not fit.
+    const fn from_wide(numerator: u128, denominator: u128) -> Self {
+        let divisor = gcd_wide(numerator, denominator);
+        let numerator = numerator / divisor;
+        let denominator = denominator / divisor;
+        assert!(
+            numerator <= u64::MAX as u128 && denominator <= u64::MAX as u128,
+            "fraction overflow"
+        );
+        Self {
+            numerator: numerator as u64,
+            denominator: denominator as u64,
+        }
+    }
+}
+
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a == 0 {
+        1
+    } else {
+        a
+    }
+}
+
+const fn gcd_wide(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a == 0 {
+        1
+    } else {
+        a
+    }
+}
+
+impl Default for Fraction {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.numerator as u128 * other.denominator as u128;
+        let right = other.numerator as u128 * self.denominator as u128;
+        left.cmp(&right)
+    }
+}
+
+impl Add for Fraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_wide(
+            self.numerator as u128 * rhs.denominator as u128
+                + rhs.numerator as u128 * self.denominator as u128,
+            self.denominator as u128 * rhs.denominator as u128,
+        )
+    }
+}
+
+impl Sub for Fraction {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if `rhs` is longer than `self`; use [`Fraction::checked_sub`] to avoid this.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("cannot subtract a longer length from a shorter one")
+    }
+}
+
+impl Mul for Fraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_wide(
+            self.numerator as u128 * rhs.numerator as u128,
+            self.denominator as u128 * rhs.denominator as u128,
+        )
+    }
+}
+
+impl Mul<u64> for Fraction {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self::Output {
+        self * Self::new(rhs, 1)
+    }
+}
+
+impl Sum for Fraction {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Display for Fraction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
 }
 
 #[cfg(feature = "midi")]
@@ -106,30 +576,114 @@ mod midi {
     use crate::duration::Duration;
     use crate::prelude::PrimitiveDuration;
 
+    /// MIDI ticks in a whole note.
+    const WHOLE_TICKS: u64 = 4096;
+
     impl PrimitiveDuration {
         pub fn to_midi(&self) -> u32 {
-            match self {
-                &Self::WHOLE => 8192,
-                &Self::HALF => 2048,
-                &Self::QUARTER => 1024,
-                &Self::EIGHTH => 512,
-                &Self::SIXTEENTH => 256,
-                &Self::THIRTY_SECOND => 128,
-                &Self::SIXTY_FOURTH => 64,
-                _ => unimplemented!("Unsupported time signature: {:?}", self),
-            }
+            self.length()
+                .ticks(WHOLE_TICKS / 4)
+                .expect("every note value is a whole number of ticks") as u32
         }
     }
 
     impl Duration {
+        /// The length in MIDI ticks, rounded to the nearest tick for tuplets that do not divide evenly.
         pub fn to_midi(&self) -> u32 {
-            let mut primitive = self.primitive.to_midi();
-            let mut value = primitive;
-            for _ in 0..self.dots {
-                value += primitive / 2;
-                primitive /= 2;
+            let length = self.length();
+            let ticks = length.numerator() * WHOLE_TICKS;
+            ((ticks + length.denominator() / 2) / length.denominator()) as u32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction() {
+        let third = Fraction::new(2, 6);
+        assert_eq!((third.numerator(), third.denominator()), (1, 3));
+        assert_eq!(third + third + third, Fraction::ONE);
+        assert_eq!(Fraction::ONE - third, third * 2);
+        assert!(Fraction::ONE.checked_sub(Fraction::new(4, 3)).is_none());
+        assert!(Fraction::new(1, 3) > Fraction::new(1, 4));
+        assert_eq!(third.ticks(480), Some(640));
+        assert_eq!(Fraction::new(1, 7).ticks(480), None);
+        assert_eq!(Fraction::from_ticks(720, 480), Fraction::new(3, 8));
+    }
+
+    #[test]
+    fn test_duration_round_trip() {
+        let mut primitive = PrimitiveDuration::LONGEST;
+        loop {
+            for dots in 0..4 {
+                let duration = Duration::new(primitive, dots);
+                assert_eq!(Duration::try_from(duration.length()), Ok(duration));
+            }
+            if primitive == PrimitiveDuration::SHORTEST {
+                break;
             }
-            value
+            primitive = primitive.half();
+        }
+        assert!(Duration::QUARTER < Duration::HALF);
+        assert_eq!(
+            Duration::QUARTER + Duration::QUARTER,
+            Duration::HALF.length()
+        );
+        assert_eq!(Duration::try_from(Fraction::new(2, 1)), Ok(Duration::BREVE));
+        assert!(Duration::try_from(Fraction::new(8, 1)).is_err());
+        assert!(Duration::try_from(Fraction::new(1, 512)).is_err());
+        assert!(Duration::try_from(Fraction::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_tuplets() {
+        let quintuplet = Duration::SIXTEENTH.with_tuplet(Tuplet::QUINTUPLET).unwrap();
+        assert_eq!(quintuplet.length() * 5, Duration::QUARTER.length());
+        let septuplet = Duration::SIXTEENTH.with_tuplet(Tuplet::SEPTUPLET).unwrap();
+        assert_eq!(septuplet.length() * 7, Duration::QUARTER.length());
+        let nested = Duration::EIGHTH
+            .with_tuplet(Tuplet::QUINTUPLET)
+            .and_then(|duration| duration.with_tuplet(Tuplet::TRIPLET))
+            .unwrap();
+        assert_eq!(nested.tuplet, Some(Tuplet::new(15, 8)));
+        assert_eq!(nested.length(), Fraction::new(1, 15));
+        // Deep nesting runs out of room rather than overflowing.
+        let deep = (0..11).try_fold(Duration::EIGHTH, |duration, _| {
+            duration.with_tuplet(Tuplet::TRIPLET)
+        });
+        assert_eq!(deep, None);
+        assert_eq!(Tuplet::new(256, 255).nest(Tuplet::new(256, 255)), None);
+        // Tuplets with equal lengths are ordered but not equal.
+        let sextuplet = Duration::SIXTEENTH.with_tuplet(Tuplet::SEXTUPLET).unwrap();
+        let triplet = Duration::SIXTEENTH.with_tuplet(Tuplet::TRIPLET).unwrap();
+        assert_eq!(sextuplet.length(), triplet.length());
+        assert_ne!(sextuplet.cmp(&triplet), Ordering::Equal);
+        assert!(Duration::try_from(triplet.length()).is_err());
+    }
+
+    #[test]
+    fn test_primitive_range() {
+        assert_eq!(
+            PrimitiveDuration::try_from(256),
+            Ok(PrimitiveDuration::TWO_HUNDRED_FIFTY_SIXTH)
+        );
+        assert!(PrimitiveDuration::try_from(512).is_err());
+        assert_eq!(PrimitiveDuration::LONGA.length(), Fraction::new(4, 1));
+        // The longest and shortest durations still fit with every dot in the largest tuplet.
+        let tuplet = Tuplet::new(u16::MAX, u16::MAX - 1);
+        for primitive in [PrimitiveDuration::LONGA, PrimitiveDuration::SHORTEST] {
+            let dotted = Duration::new(primitive, Duration::MAX_DOTS);
+            let length = dotted.with_tuplet(tuplet).unwrap().length();
+            assert!(length < dotted.length());
         }
+        assert_eq!(PrimitiveDuration::LONGA.double(), PrimitiveDuration::LONGA);
+        assert_eq!(
+            PrimitiveDuration::SHORTEST.half(),
+            PrimitiveDuration::SHORTEST
+        );
+        assert_eq!(PrimitiveDuration::HUNDRED_TWENTY_EIGHTH.value(), Some(128));
     }
 }
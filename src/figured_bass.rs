@@ -0,0 +1,630 @@
+//! Figured bass and its realization in four parts.
+
+use crate::chord::Chord;
+use crate::duration::{Duration, Fraction};
+use crate::key::{Key, Scale};
+use crate::measure::{
+    Measure, MeasureKind, MultiExtendedNotation, Notation, Placement, Tied, TimedMeasureItem,
+};
+use crate::note::Note;
+use crate::{Accidental, Alphabet, Clef, TimeSignature};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A single figure above a bass note, like ♯6 or 4+.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Figure {
+    /// The interval above the bass, or `None` for an accidental on its own, which applies to the third.
+    pub number: Option<u8>,
+    /// Raises, lowers or cancels the key signature for the interval, where [`Accidental::None`] leaves it.
+    pub accidental: Accidental,
+    /// A slash through the number (or a `+`), which raises the interval by a half step.
+    pub slash: bool,
+    /// An extender line, holding the figure over the following bass notes.
+    pub extend: bool,
+}
+
+impl Figure {
+    pub const fn new(number: u8) -> Self {
+        Self {
+            number: Some(number),
+            accidental: Accidental::None,
+            slash: false,
+            extend: false,
+        }
+    }
+
+    /// The alteration of the figure, in half steps from the key signature.
+    ///
+    /// `None` means the interval is taken from the key signature.
+    const fn alteration(&self) -> Option<i8> {
+        match (self.accidental, self.slash) {
+            (_, true) | (Accidental::Sharp, _) => Some(1),
+            (Accidental::Flat, _) => Some(-1),
+            (Accidental::DoubleSharp, _) => Some(2),
+            (Accidental::DoubleFlat, _) => Some(-2),
+            (Accidental::Natural, _) => Some(0),
+            (Accidental::None, _) => None,
+        }
+    }
+}
+
+impl Display for Figure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.accidental {
+            Accidental::None => {}
+            Accidental::Natural => write!(f, "♮")?,
+            accidental => write!(f, "{}", accidental.unicode())?,
+        }
+        if let Some(number) = self.number {
+            write!(f, "{}", number)?;
+        }
+        if self.slash {
+            write!(f, "+")?;
+        }
+        if self.extend {
+            write!(f, "_")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Figure {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = s.trim();
+        let mut figure = Self::default();
+        if let Some(rest) = s.strip_suffix('_') {
+            figure.extend = true;
+            s = rest;
+        }
+        if let Some(rest) = s.strip_suffix(['+', '\\']) {
+            figure.slash = true;
+            s = rest;
+        }
+        let digits = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        figure.accidental = match &s[..digits] {
+            "" => Accidental::None,
+            "#" | "♯" => Accidental::Sharp,
+            "b" | "♭" => Accidental::Flat,
+            "n" | "♮" => Accidental::Natural,
+            "##" | "x" | "𝄪" => Accidental::DoubleSharp,
+            "bb" | "𝄫" => Accidental::DoubleFlat,
+            _ => return Err(()),
+        };
+        if digits < s.len() {
+            let number = s[digits..].parse::<u8>().map_err(|_| ())?;
+            if !(2..=15).contains(&number) {
+                return Err(());
+            }
+            figure.number = Some(number);
+        } else if figure.accidental == Accidental::None {
+            return Err(());
+        }
+        Ok(figure)
+    }
+}
+
+/// The figures written under a bass note, from top to bottom.
+///
+/// Figures are written separated by `/`, like 6/4, with accidentals before the number,
+/// `+` for a slashed figure and `_` for an extender.
+/// No figures at all stand for a root position triad.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::figured_bass::FiguredBass;
+/// let figures = "6/5".parse::<FiguredBass>().unwrap();
+/// assert_eq!(figures.intervals(), vec![6, 5, 3]);
+/// let figures = "#".parse::<FiguredBass>().unwrap();
+/// assert_eq!(figures.intervals(), vec![5, 3]);
+/// assert_eq!(figures.to_string(), "♯");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiguredBass {
+    pub figures: Vec<Figure>,
+}
+
+impl FiguredBass {
+    pub const fn new(figures: Vec<Figure>) -> Self {
+        Self { figures }
+    }
+
+    /// The intervals above the bass that the figures call for, including the ones left implied, largest first.
+    ///
+    /// 6 implies 6/3, 7 implies 7/5/3, 6/5 implies 6/5/3, 4/3 implies 6/4/3 and 2 implies 6/4/2.
+    pub fn intervals(&self) -> Vec<u8> {
+        let mut intervals = self
+            .figures
+            .iter()
+            .filter_map(|figure| figure.number)
+            .collect::<Vec<_>>();
+        let has = |intervals: &[u8], number: u8| intervals.contains(&number);
+        let implied: &[u8] = match intervals.as_slice() {
+            [] | [5] | [8] | [5, 3] => &[5, 3],
+            [6] | [6, 3] => &[6, 3],
+            [7] => &[7, 5, 3],
+            [6, 5] => &[6, 5, 3],
+            [4, 3] => &[6, 4, 3],
+            [2] | [4, 2] => &[6, 4, 2],
+            [9] => &[9, 5, 3],
+            [4] => &[5, 4],
+            _ => &[],
+        };
+        for &number in implied {
+            if !has(&intervals, number) {
+                intervals.push(number);
+            }
+        }
+        intervals.sort_unstable_by(|a, b| b.cmp(a));
+        intervals
+    }
+
+    /// Spells the notes above `bass` in `key`, each with whether it is the chord's dissonance, largest interval first.
+    ///
+    /// Returns `None` in a chromatic key or if a figure cannot be spelled.
+    fn notes(&self, bass: &Note, key: &Key) -> Option<Vec<(Note, bool)>> {
+        let Key::Diatonic { .. } = key else {
+            return None;
+        };
+        let scale = Scale::from(key);
+        let intervals = self.intervals();
+        let dissonance = match intervals.as_slice() {
+            [7, ..] => Some(7),
+            [6, 5, 3] => Some(5),
+            [6, 4, 3] => Some(3),
+            [9, ..] => Some(9),
+            [5, 4] => Some(4),
+            _ => None,
+        };
+        intervals
+            .iter()
+            .map(|&number| {
+                let alphabet = bass.alphabet.shift(number as i64 - 1);
+                let figure = self
+                    .figures
+                    .iter()
+                    .find(|figure| figure.number == Some(number))
+                    .or_else(|| {
+                        // An accidental on its own belongs to the third.
+                        self.figures
+                            .iter()
+                            .find(|figure| figure.number.is_none() && number == 3)
+                    });
+                let note = spell(&scale, alphabet, figure.and_then(Figure::alteration))?;
+                Some((note, dissonance == Some(number)))
+            })
+            .collect()
+    }
+}
+
+/// Spells a letter from the key signature, then applies a figure's alteration.
+fn spell(scale: &Scale, alphabet: Alphabet, alteration: Option<i8>) -> Option<Note> {
+    let note = scale.notes.iter().find(|note| note.alphabet == alphabet)?;
+    match alteration {
+        None => Some(*note),
+        Some(0) => Some(Note::new(alphabet, Accidental::Natural, note.octave)),
+        Some(alteration) => note.altered(alteration),
+    }
+}
+
+impl Display for FiguredBass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, figure) in self.figures.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{}", figure)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for FiguredBass {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        s.split('/')
+            .map(Figure::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+}
+
+/// A bass note with its figures.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiguredNote {
+    pub note: Note,
+    pub figures: FiguredBass,
+    pub duration: Duration,
+}
+
+impl FiguredNote {
+    pub const fn new(note: Note, figures: FiguredBass, duration: Duration) -> Self {
+        Self {
+            note,
+            figures,
+            duration,
+        }
+    }
+}
+
+/// One way to voice the upper parts over a bass note.
+#[derive(Clone, Debug)]
+struct Voicing {
+    /// Tenor, alto and soprano.
+    notes: [Note; 3],
+    /// Which of the upper voices hold the chord's dissonance.
+    dissonant: [bool; 3],
+    /// The cost of the voicing on its own.
+    cost: u32,
+}
+
+/// The lowest and highest notes of the tenor, alto and soprano.
+///
+/// The tenor goes from the C below middle C to the G above it, the alto from the G below middle C
+/// to the D an octave and a step above it, and the soprano from middle C to the G an octave and a fifth above it.
+fn ranges() -> [(Note, Note); 3] {
+    [
+        (
+            Note::new(Alphabet::C, Accidental::Natural, 2),
+            Note::new(Alphabet::G, Accidental::Natural, 3),
+        ),
+        (
+            Note::new(Alphabet::G, Accidental::Natural, 2),
+            Note::new(Alphabet::D, Accidental::Natural, 4),
+        ),
+        (
+            Note::new(Alphabet::C, Accidental::Natural, 3),
+            Note::new(Alphabet::G, Accidental::Natural, 4),
+        ),
+    ]
+}
+
+/// Lists the voicings over a bass note, keeping the voices in order and within an octave of each other.
+fn voicings(bass: &Note, tones: &[(Note, bool)], key: &Key) -> Vec<Voicing> {
+    let leading_tone = match key {
+        Key::Diatonic { root, .. } => Some(root.id().0 - 1),
+        Key::Chromatic => None,
+    }
+    .map(|id| crate::pitch::Pitch(id).simple());
+    let chromatic = |note: &Note| {
+        let scale = Scale::from(key);
+        !scale
+            .notes
+            .iter()
+            .any(|scale_note| scale_note.id().simple() == note.id().simple())
+    };
+    // The bass doubles freely, and the fifth of a triad or seventh chord may be left out.
+    let mut pool = vec![(*bass, false)];
+    pool.extend(tones.iter().copied());
+    let omittable = tones.len() >= 2 && tones.iter().any(|(note, _)| is_fifth(bass, note));
+    let candidates = |(low, high): (Note, Note)| {
+        pool.iter()
+            .enumerate()
+            .flat_map(|(i, (note, dissonant))| {
+                (0..=8u8).map(move |octave| (i, Note { octave, ..*note }, *dissonant))
+            })
+            .filter(|(_, note, _)| low.id() <= note.id() && note.id() <= high.id())
+            .collect::<Vec<_>>()
+    };
+    let [tenor, alto, soprano] = ranges().map(candidates);
+    let mut voicings = vec![];
+    for &(ti, t, td) in tenor.iter().filter(|(_, t, _)| t.id() >= bass.id()) {
+        for &(ai, a, ad) in alto.iter().filter(|(_, a, _)| a.id() > t.id()) {
+            if a.id().0 - t.id().0 > 12 {
+                continue;
+            }
+            for &(si, s, sd) in soprano.iter().filter(|(_, s, _)| s.id() > a.id()) {
+                if s.id().0 - a.id().0 > 12 {
+                    continue;
+                }
+                let used = [ti, ai, si];
+                let missing = (1..pool.len())
+                    .filter(|i| !used.contains(i))
+                    .collect::<Vec<_>>();
+                let mut cost = match missing.as_slice() {
+                    [] => 0,
+                    [i] if omittable && is_fifth(bass, &pool[*i].0) => 5,
+                    _ => continue,
+                };
+                let notes = [t, a, s];
+                let dissonant = [td, ad, sd];
+                // Dissonances and tendency tones should not be doubled.
+                for (i, note) in notes.iter().enumerate() {
+                    let doubled = notes[..i]
+                        .iter()
+                        .chain([bass])
+                        .any(|other| other.id().simple() == note.id().simple());
+                    if !doubled {
+                        continue;
+                    }
+                    if dissonant[i] || Some(note.id().simple()) == leading_tone || chromatic(note) {
+                        cost += 20;
+                    } else if note.id().simple() != bass.id().simple() {
+                        cost += 2;
+                    }
+                }
+                voicings.push(Voicing {
+                    notes,
+                    dissonant,
+                    cost,
+                });
+            }
+        }
+    }
+    voicings
+}
+
+fn is_fifth(bass: &Note, note: &Note) -> bool {
+    bass.alphabet.shift(4) == note.alphabet
+}
+
+/// The cost of moving from one voicing to the next.
+fn transition(from_bass: &Note, from: &Voicing, to_bass: &Note, to: &Voicing) -> u32 {
+    let mut cost = 0;
+    for i in 0..3 {
+        let motion = (to.notes[i].id().0 - from.notes[i].id().0).unsigned_abs() as u32;
+        cost += motion;
+        if motion > 7 {
+            cost += 5;
+        }
+        // A dissonance resolves down by step.
+        if from.dissonant[i] && !(1..=2).contains(&(from.notes[i].id().0 - to.notes[i].id().0)) {
+            cost += 10;
+        }
+    }
+    let from_voices = [*from_bass, from.notes[0], from.notes[1], from.notes[2]];
+    let to_voices = [*to_bass, to.notes[0], to.notes[1], to.notes[2]];
+    for low in 0..4 {
+        for high in low + 1..4 {
+            let before = (from_voices[high].id().0 - from_voices[low].id().0).rem_euclid(12);
+            let after = (to_voices[high].id().0 - to_voices[low].id().0).rem_euclid(12);
+            let moved = from_voices[low].id() != to_voices[low].id();
+            if before == after && (after == 0 || after == 7) && moved {
+                cost += 100;
+            }
+        }
+    }
+    cost
+}
+
+/// Realizes a figured bass in four parts, returning the tenor, alto and soprano over each bass note.
+///
+/// The upper voices stay in order, within an octave of each other and in their usual ranges,
+/// and are chosen to move as little as possible while avoiding parallel fifths and octaves,
+/// resolving sevenths down by step and not doubling the leading tone or chromatic notes.
+/// Returns `None` in a chromatic key or if a bass note cannot be realized.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::figured_bass::{realize, FiguredNote};
+/// use note_pen::prelude::*;
+/// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+/// let bass = [("C2", ""), ("F2", "6/4"), ("G1", "7"), ("C2", "")]
+///     .map(|(note, figures)| {
+///         FiguredNote::new(note.parse().unwrap(), figures.parse().unwrap(), Duration::QUARTER)
+///     });
+/// let chords = realize(&bass, &key).unwrap();
+/// assert_eq!(chords.len(), 4);
+/// assert!(chords.iter().all(|chord| chord.notes.len() == 3));
+/// ```
+pub fn realize(bass: &[FiguredNote], key: &Key) -> Option<Vec<Chord>> {
+    let mut steps: Vec<Vec<Voicing>> = vec![];
+    for figured in bass {
+        let tones = figured.figures.notes(&figured.note, key)?;
+        let voicings = voicings(&figured.note, &tones, key);
+        if voicings.is_empty() {
+            return None;
+        }
+        steps.push(voicings);
+    }
+    let Some(first) = steps.first() else {
+        return Some(vec![]);
+    };
+    // Find the cheapest path through the voicings.
+    let mut costs = first.iter().map(|voicing| voicing.cost).collect::<Vec<_>>();
+    let mut previous: Vec<Vec<usize>> = vec![vec![]];
+    for i in 1..steps.len() {
+        let mut next_costs = vec![];
+        let mut next_previous = vec![];
+        for to in steps[i].iter() {
+            let (best, cost) = steps[i - 1]
+                .iter()
+                .enumerate()
+                .map(|(j, from)| {
+                    let cost =
+                        costs[j] + transition(&bass[i - 1].note, from, &bass[i].note, to) + to.cost;
+                    (j, cost)
+                })
+                .min_by_key(|&(_, cost)| cost)?;
+            next_costs.push(cost);
+            next_previous.push(best);
+        }
+        costs = next_costs;
+        previous.push(next_previous);
+    }
+    let mut index = (0..costs.len()).min_by_key(|&i| costs[i])?;
+    let mut chords = vec![];
+    for i in (0..steps.len()).rev() {
+        chords.push(Chord::new(steps[i][index].notes.to_vec()));
+        if i > 0 {
+            index = previous[i][index];
+        }
+    }
+    chords.reverse();
+    Some(chords)
+}
+
+/// Realizes a figured bass with [`realize`] and writes the upper voices on a treble staff,
+/// filling measures of the time signature in order.
+///
+/// A chord that crosses a barline is split into tied chords with [`TimeSignature::split`].
+/// Returns `None` if the bass cannot be realized, or a chord that crosses a barline cannot be split.
+pub fn realize_measures(
+    bass: &[FiguredNote],
+    key: &Key,
+    time_signature: TimeSignature,
+) -> Option<Vec<Measure>> {
+    let chords = realize(bass, key)?;
+    let new_measure = || Measure::new(Clef::Treble, time_signature.clone(), key.clone());
+    let measure_length = time_signature.measure_length();
+    let mut measures = vec![];
+    let mut measure = new_measure();
+    let mut position = Fraction::ZERO;
+    let mut ties = 0;
+    for (chord, figured) in chords.into_iter().zip(bass) {
+        let length = figured.duration.length();
+        let fits = measure_length.is_zero() || position + length <= measure_length;
+        let durations = if fits {
+            vec![figured.duration]
+        } else {
+            time_signature.split(position, length)?
+        };
+        let last = durations.len() - 1;
+        for (i, duration) in durations.into_iter().enumerate() {
+            let mut item = TimedMeasureItem::chord(chord.clone(), duration);
+            if last > 0 {
+                let tie = match i {
+                    0 => MultiExtendedNotation::Start {
+                        placement: Placement::Above,
+                        id: ties,
+                        details: Tied {},
+                    },
+                    i if i == last => MultiExtendedNotation::Stop(ties),
+                    _ => MultiExtendedNotation::Continue(ties),
+                };
+                item = item.with_notation(Notation::Tied(tie));
+            }
+            measure.push(item);
+            position = position + duration.length();
+            if !measure_length.is_zero() && position >= measure_length {
+                measures.push(std::mem::replace(&mut measure, new_measure()));
+                position = Fraction::ZERO;
+            }
+        }
+        if last > 0 {
+            ties = ties.wrapping_add(1);
+        }
+    }
+    if !measure.notes.is_empty() {
+        if !measure_length.is_zero() {
+            measure.kind = MeasureKind::Incomplete;
+        }
+        measures.push(measure);
+    }
+    Some(measures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn figured(note: &str, figures: &str) -> FiguredNote {
+        FiguredNote::new(
+            note.parse().unwrap(),
+            figures.parse().unwrap(),
+            Duration::QUARTER,
+        )
+    }
+
+    #[test]
+    fn test_figures() {
+        for figures in ["", "6", "6/4", "♯6/4+", "7_", "♮", "♭7/5"] {
+            assert_eq!(figures.parse::<FiguredBass>().unwrap().to_string(), figures);
+        }
+        assert!("6/y".parse::<FiguredBass>().is_err());
+        assert!("1".parse::<FiguredBass>().is_err());
+        let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        // A sharp on its own raises the third, giving the leading tone over E.
+        let notes = "#"
+            .parse::<FiguredBass>()
+            .unwrap()
+            .notes(&"E3".parse().unwrap(), &key)
+            .unwrap();
+        assert_eq!(notes[1].0.alphabet, Alphabet::G);
+        assert_eq!(notes[1].0.accidental, Accidental::Sharp);
+    }
+
+    #[test]
+    fn test_realize() {
+        let key = Key::new_major(Note::new(Alphabet::D, Accidental::Natural, 4)).unwrap();
+        let bass = [
+            figured("D2", ""),
+            figured("G2", ""),
+            figured("A2", "7"),
+            figured("D2", ""),
+        ];
+        let chords = realize(&bass, &key).unwrap();
+        let voices = |i: usize| {
+            chords
+                .iter()
+                .zip(bass.iter())
+                .map(|(chord, bass)| {
+                    if i == 0 {
+                        bass.note
+                    } else {
+                        chord.notes[i - 1]
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        // No parallel fifths or octaves between any pair of voices.
+        for low in 0..4 {
+            for high in low + 1..4 {
+                let (low, high) = (voices(low), voices(high));
+                for step in 1..bass.len() {
+                    let before = (high[step - 1].id().0 - low[step - 1].id().0).rem_euclid(12);
+                    let after = (high[step].id().0 - low[step].id().0).rem_euclid(12);
+                    let moved = low[step - 1].id() != low[step].id();
+                    assert!(!(moved && before == after && (after == 0 || after == 7)));
+                }
+            }
+        }
+        // The seventh over A is G, which resolves down to F♯.
+        let seventh = chords[2]
+            .notes
+            .iter()
+            .position(|note| note.alphabet == Alphabet::G)
+            .unwrap();
+        let resolution = chords[3].notes[seventh];
+        assert_eq!(resolution.alphabet, Alphabet::F);
+        assert_eq!(resolution.accidental, Accidental::Sharp);
+        let measures = realize_measures(&bass, &key, TimeSignature::new(1, 4)).unwrap();
+        assert_eq!(measures.len(), 4);
+        let measures = realize_measures(&bass, &key, TimeSignature::new(3, 4)).unwrap();
+        assert_eq!(measures.len(), 2);
+        assert_eq!(measures[0].notes.len(), 3);
+        assert_eq!(measures[1].kind, MeasureKind::Incomplete);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let bass = [
+            figured("C2", ""),
+            figured("F2", "6/4"),
+            figured("G1", "7"),
+            figured("C2", ""),
+        ];
+        // In MIDI note numbers, the tenor starts at 48, the C below middle C,
+        // and the soprano goes from 60, middle C, up to 79.
+        let midi = |note: &Note| note.id().0 + 69;
+        for chord in realize(&bass, &key).unwrap() {
+            let [tenor, alto, soprano] = [0, 1, 2].map(|i| midi(&chord.notes[i]));
+            assert!((48..=67).contains(&tenor));
+            assert!((55..=74).contains(&alto));
+            assert!((60..=79).contains(&soprano));
+        }
+        let figures = "6".parse::<FiguredBass>().unwrap();
+        let item = TimedMeasureItem::note(bass[0].note, Duration::QUARTER).with_figures(figures);
+        assert_eq!(item.figures().unwrap().intervals(), vec![6, 3]);
+    }
+}
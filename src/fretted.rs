@@ -0,0 +1,394 @@
+//! Chord shapes for fretted string instruments such as the guitar, bass, ukulele and banjo.
+
+use crate::chord::Chord;
+use crate::note::Note;
+use crate::pitch::{Pitch, RelativePitch};
+use crate::{Accidental, Alphabet};
+use std::collections::HashSet;
+
+/// The open-string pitches of a fretted instrument, from the lowest-numbered (bass side) string up.
+///
+/// Strings do not have to be in pitch order, so re-entrant tunings like the ukulele work.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuning {
+    pub strings: Vec<Note>,
+}
+
+impl Tuning {
+    pub const fn new(strings: Vec<Note>) -> Self {
+        Self { strings }
+    }
+
+    /// Standard six-string guitar tuning: E A D G B E.
+    pub fn guitar() -> Self {
+        Self::new(vec![
+            Note::new(Alphabet::E, Accidental::Natural, 1),
+            Note::new(Alphabet::A, Accidental::Natural, 2),
+            Note::new(Alphabet::D, Accidental::Natural, 2),
+            Note::new(Alphabet::G, Accidental::Natural, 2),
+            Note::new(Alphabet::B, Accidental::Natural, 3),
+            Note::new(Alphabet::E, Accidental::Natural, 3),
+        ])
+    }
+
+    /// Standard four-string bass tuning: E A D G, an octave below the guitar.
+    pub fn bass() -> Self {
+        Self::new(vec![
+            Note::new(Alphabet::E, Accidental::Natural, 0),
+            Note::new(Alphabet::A, Accidental::Natural, 1),
+            Note::new(Alphabet::D, Accidental::Natural, 1),
+            Note::new(Alphabet::G, Accidental::Natural, 1),
+        ])
+    }
+
+    /// Standard re-entrant ukulele tuning: G C E A.
+    pub fn ukulele() -> Self {
+        Self::new(vec![
+            Note::new(Alphabet::G, Accidental::Natural, 3),
+            Note::new(Alphabet::C, Accidental::Natural, 3),
+            Note::new(Alphabet::E, Accidental::Natural, 3),
+            Note::new(Alphabet::A, Accidental::Natural, 4),
+        ])
+    }
+
+    /// Open G tuning for a five-string banjo: g D G B D, with the short fifth string listed first.
+    pub fn banjo() -> Self {
+        Self::new(vec![
+            Note::new(Alphabet::G, Accidental::Natural, 3),
+            Note::new(Alphabet::D, Accidental::Natural, 2),
+            Note::new(Alphabet::G, Accidental::Natural, 2),
+            Note::new(Alphabet::B, Accidental::Natural, 3),
+            Note::new(Alphabet::D, Accidental::Natural, 3),
+        ])
+    }
+
+    /// Returns the note sounded by a string at a fret, or `None` if there is no such string.
+    pub fn note(&self, string: usize, fret: u8) -> Option<Note> {
+        self.strings
+            .get(string)
+            .map(|open| Note::from_id(Pitch(open.id().0 + fret as i16)))
+    }
+
+    /// Finds playable shapes for a chord, easiest first.
+    ///
+    /// Every pitch class of the chord is sounded at least once and no other pitch class is sounded.
+    /// # Examples
+    /// ```rust
+    /// use note_pen::fretted::{ShapeSearch, Tuning};
+    /// use note_pen::prelude::*;
+    /// let c_major = Chord::triad_from_root(
+    ///     Tonality::Major,
+    ///     Note::new(Alphabet::C, Accidental::Natural, 3),
+    ///     Inversion::ROOT,
+    /// );
+    /// let shapes = Tuning::guitar().shapes(&c_major, &ShapeSearch::default());
+    /// // The open C chord: x32010
+    /// assert_eq!(shapes[0].frets, vec![None, Some(3), Some(2), Some(0), Some(1), Some(0)]);
+    /// assert!(shapes[0].barre().is_none());
+    /// ```
+    pub fn shapes(&self, chord: &Chord, search: &ShapeSearch) -> Vec<Fingering> {
+        let pitches = chord
+            .notes
+            .iter()
+            .map(|note| note.id().simple())
+            .collect::<HashSet<_>>();
+        let root = chord.notes.first().map(|note| note.id().simple());
+        let mut seen = HashSet::new();
+        let mut shapes = vec![];
+        for base in 1..=search.max_fret.max(1) {
+            let top = base.saturating_add(search.hand_span.saturating_sub(1));
+            let candidates = self
+                .strings
+                .iter()
+                .map(|open| {
+                    let mut frets = vec![None];
+                    frets.extend(
+                        std::iter::once(0)
+                            .chain(base..=top.min(search.max_fret))
+                            .filter(|&fret| {
+                                pitches.contains(&Pitch(open.id().0 + fret as i16).simple())
+                            })
+                            .map(Some),
+                    );
+                    frets
+                })
+                .collect::<Vec<_>>();
+            let mut current = Vec::with_capacity(self.strings.len());
+            self.search(&candidates, &mut current, &mut |frets| {
+                let fingering = Fingering::new(frets.to_vec());
+                if seen.contains(&fingering.frets) {
+                    return;
+                }
+                let sounding = fingering.sounding(self);
+                let covered = sounding
+                    .iter()
+                    .map(|note| note.id().simple())
+                    .collect::<HashSet<_>>();
+                if covered.len() == pitches.len()
+                    && sounding.len() >= search.min_strings.min(self.strings.len())
+                    && fingering.fingers() <= 4
+                {
+                    seen.insert(fingering.frets.clone());
+                    shapes.push(fingering);
+                }
+            });
+        }
+        shapes.sort_by_key(|fingering| (fingering.difficulty(self, root), fingering.frets.clone()));
+        shapes
+    }
+
+    fn search(
+        &self,
+        candidates: &[Vec<Option<u8>>],
+        current: &mut Vec<Option<u8>>,
+        found: &mut impl FnMut(&[Option<u8>]),
+    ) {
+        if current.len() == candidates.len() {
+            found(current);
+            return;
+        }
+        for &fret in candidates[current.len()].iter() {
+            current.push(fret);
+            self.search(candidates, current, found);
+            current.pop();
+        }
+    }
+}
+
+/// Limits on the shapes returned by [`Tuning::shapes`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeSearch {
+    /// The highest fret that may be used.
+    pub max_fret: u8,
+    /// The number of frets the hand can cover at once, including the lowest fretted one.
+    pub hand_span: u8,
+    /// The fewest strings that must sound, capped at the number of strings.
+    pub min_strings: usize,
+}
+
+impl Default for ShapeSearch {
+    fn default() -> Self {
+        Self {
+            max_fret: 12,
+            hand_span: 4,
+            min_strings: 3,
+        }
+    }
+}
+
+/// A barre, where one finger presses several adjacent strings at the same fret.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Barre {
+    pub fret: u8,
+    /// The first string covered, as an index into [`Tuning::strings`].
+    pub from_string: usize,
+    /// The last string covered, inclusive.
+    pub to_string: usize,
+}
+
+/// A chord shape: the fret pressed on each string.
+///
+/// `None` means the string is muted and `Some(0)` means it is played open.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingering {
+    pub frets: Vec<Option<u8>>,
+}
+
+impl Fingering {
+    pub const fn new(frets: Vec<Option<u8>>) -> Self {
+        Self { frets }
+    }
+
+    /// Returns the notes that sound, in string order.
+    pub fn sounding(&self, tuning: &Tuning) -> Vec<Note> {
+        self.frets
+            .iter()
+            .enumerate()
+            .filter_map(|(string, fret)| fret.and_then(|fret| tuning.note(string, fret)))
+            .collect()
+    }
+
+    /// Converts the shape back into a chord, from the lowest sounding pitch up.
+    pub fn chord(&self, tuning: &Tuning) -> Chord {
+        let mut notes = self.sounding(tuning);
+        notes.sort_by_key(|note| note.id());
+        Chord::new(notes)
+    }
+
+    fn fretted(&self) -> impl Iterator<Item = (usize, u8)> + '_ {
+        self.frets
+            .iter()
+            .enumerate()
+            .filter_map(|(string, fret)| fret.filter(|&fret| fret > 0).map(|fret| (string, fret)))
+    }
+
+    /// The distance between the lowest and highest fretted notes, counting both, or 0 if nothing is fretted.
+    pub fn span(&self) -> u8 {
+        let min = self.fretted().map(|(_, fret)| fret).min();
+        let max = self.fretted().map(|(_, fret)| fret).max();
+        match (min, max) {
+            (Some(min), Some(max)) => max - min + 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the barre needed to play the shape, if there are more fretted notes than fingers.
+    ///
+    /// The barre is placed on the lowest fretted fret and covers every string from the first to
+    /// the last one pressed at that fret; it is only possible when none of those strings are open or muted.
+    pub fn barre(&self) -> Option<Barre> {
+        if self.fretted().count() <= 4 {
+            return None;
+        }
+        let fret = self.fretted().map(|(_, fret)| fret).min()?;
+        let mut strings = self
+            .fretted()
+            .filter(|&(_, f)| f == fret)
+            .map(|(string, _)| string);
+        let from_string = strings.next()?;
+        let to_string = strings.last()?;
+        let covered = self.frets[from_string..=to_string]
+            .iter()
+            .all(|f| matches!(f, Some(f) if *f >= fret));
+        covered.then_some(Barre {
+            fret,
+            from_string,
+            to_string,
+        })
+    }
+
+    /// The number of fingers needed, counting a barre as one finger.
+    ///
+    /// Shapes with more than five fretted notes that cannot be barred need an impossible number of fingers.
+    pub fn fingers(&self) -> usize {
+        let fretted = self.fretted().count();
+        match self.barre() {
+            Some(barre) => {
+                1 + self
+                    .fretted()
+                    .filter(|&(string, fret)| {
+                        fret != barre.fret
+                            || !(barre.from_string..=barre.to_string).contains(&string)
+                    })
+                    .count()
+            }
+            None if fretted <= 4 => fretted,
+            None => usize::MAX,
+        }
+    }
+
+    /// A score where lower is easier, used to rank the results of [`Tuning::shapes`].
+    ///
+    /// It penalizes wide stretches, high positions, barres, extra fingers, muted strings (especially above the bass),
+    /// and a lowest note that is not the chord root.
+    pub fn difficulty(&self, tuning: &Tuning, root: Option<RelativePitch>) -> u32 {
+        let mut score = 2 * self.span() as u32 + self.fingers().min(8) as u32;
+        score += self.fretted().map(|(_, fret)| fret).min().unwrap_or(0) as u32 / 2;
+        if self.barre().is_some() {
+            score += 3;
+        }
+        // Muting bass strings is common, muting inner or treble strings much less so.
+        let first = self.frets.iter().position(Option::is_some).unwrap_or(0);
+        let last = self.frets.iter().rposition(Option::is_some).unwrap_or(0);
+        for (string, fret) in self.frets.iter().enumerate() {
+            score += match fret {
+                Some(_) => 0,
+                None if string < first => 2,
+                None if string < last => 4,
+                None => 6,
+            };
+        }
+        let bass = self
+            .sounding(tuning)
+            .into_iter()
+            .min_by_key(|note| note.id());
+        if root.is_some() && bass.map(|note| note.id().simple()) != root {
+            score += 5;
+        }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chord::Inversion;
+    use crate::Tonality;
+
+    #[test]
+    fn test_open_shapes() {
+        let g_major = Chord::triad_from_root(
+            Tonality::Major,
+            Note::new(Alphabet::G, Accidental::Natural, 2),
+            Inversion::ROOT,
+        );
+        let shapes = Tuning::guitar().shapes(&g_major, &ShapeSearch::default());
+        assert!(shapes.contains(&Fingering::new(vec![
+            Some(3),
+            Some(2),
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(3)
+        ])));
+        for shape in shapes.iter() {
+            assert!(shape.span() <= 4);
+            assert!(shape.fingers() <= 4);
+        }
+
+        let c_major = Chord::triad_from_root(
+            Tonality::Major,
+            Note::new(Alphabet::C, Accidental::Natural, 3),
+            Inversion::ROOT,
+        );
+        let shapes = Tuning::ukulele().shapes(&c_major, &ShapeSearch::default());
+        assert_eq!(shapes[0].frets, vec![Some(0), Some(0), Some(0), Some(3)]);
+    }
+
+    #[test]
+    fn test_barre() {
+        // F major on the first fret: 133211
+        let f_major = Fingering::new(vec![Some(1), Some(3), Some(3), Some(2), Some(1), Some(1)]);
+        assert_eq!(
+            f_major.barre(),
+            Some(Barre {
+                fret: 1,
+                from_string: 0,
+                to_string: 5
+            })
+        );
+        assert_eq!(f_major.fingers(), 4);
+        let chord = f_major.chord(&Tuning::guitar());
+        assert_eq!(chord.notes.len(), 6);
+        assert_eq!(
+            chord.notes[0],
+            Note::new(Alphabet::F, Accidental::Natural, 1)
+        );
+
+        let b_flat = Chord::triad_from_root(
+            Tonality::Major,
+            Note::new(Alphabet::B, Accidental::Flat, 2),
+            Inversion::ROOT,
+        );
+        let shapes = Tuning::guitar().shapes(
+            &b_flat,
+            &ShapeSearch {
+                min_strings: 6,
+                ..Default::default()
+            },
+        );
+        assert!(shapes.iter().all(|shape| shape.barre().is_some()));
+        assert!(shapes.contains(&Fingering::new(vec![
+            Some(6),
+            Some(8),
+            Some(8),
+            Some(7),
+            Some(6),
+            Some(6)
+        ])));
+    }
+}
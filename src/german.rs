@@ -0,0 +1,263 @@
+//! German and Dutch note names, where sharps and flats are written as the suffixes -is and -es.
+
+use crate::note::Note;
+use crate::pitch::{RelativePitch, RelativeSystem};
+use crate::solfege::{parse_octave_marks, write_octave_marks};
+use crate::{Accidental, Alphabet, Interval};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A note named in German, where B♮ is H and B♭ is B.
+///
+/// Octaves use Helmholtz notation, so middle C is c', the octave below is c and the octave below that is C.
+/// Helmholtz octaves start at C, while the octaves of a [`Note`] start at A,
+/// so middle C is C3 and the A above it is A4.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::german::GermanNote;
+/// let note = GermanNote(Note::new(Alphabet::E, Accidental::Flat, 3));
+/// assert_eq!(note.to_string(), "es'");
+/// assert_eq!(GermanNote(Note::new(Alphabet::A, Accidental::Natural, 4)).to_string(), "a'");
+/// assert_eq!(GermanNote(Note::new(Alphabet::B, Accidental::Natural, 2)).to_string(), "H");
+/// assert_eq!("B".parse::<GermanNote>().unwrap().0.accidental, Accidental::Flat);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct GermanNote(pub Note);
+
+/// A note named in Dutch, where B♮ is B and B♭ is Bes.
+///
+/// Octaves use Helmholtz notation like [`GermanNote`].
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::german::DutchNote;
+/// let note = DutchNote(Note::new(Alphabet::B, Accidental::Flat, 3));
+/// assert_eq!(note.to_string(), "bes");
+/// assert_eq!("Fis,".parse::<DutchNote>().unwrap().0.octave, 0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct DutchNote(pub Note);
+
+/// Spells a note in lowercase without its octave.
+fn name(note: &Note, german: bool) -> String {
+    let letter = note.alphabet.to_string().to_lowercase();
+    match (note.alphabet, note.accidental, german) {
+        (Alphabet::B, Accidental::Flat, true) => "b".to_string(),
+        (Alphabet::B, Accidental::DoubleFlat, true) => "heses".to_string(),
+        (Alphabet::B, accidental, true) => format!("h{}", suffix(accidental)),
+        // Vowels absorb the e of -es.
+        (Alphabet::A | Alphabet::E, Accidental::Flat, _) => format!("{letter}s"),
+        (Alphabet::A | Alphabet::E, Accidental::DoubleFlat, _) => format!("{letter}s{letter}s"),
+        (_, accidental, _) => format!("{letter}{}", suffix(accidental)),
+    }
+}
+
+const fn suffix(accidental: Accidental) -> &'static str {
+    match accidental {
+        Accidental::DoubleFlat => "eses",
+        Accidental::Flat => "es",
+        Accidental::None | Accidental::Natural => "",
+        Accidental::Sharp => "is",
+        Accidental::DoubleSharp => "isis",
+    }
+}
+
+/// How much higher the Helmholtz octave of a letter is than its octave in a [`Note`],
+/// since Helmholtz octaves start at C rather than A.
+const fn octave_offset(alphabet: Alphabet) -> i16 {
+    match alphabet {
+        Alphabet::A | Alphabet::B => 0,
+        _ => 1,
+    }
+}
+
+fn write_helmholtz(f: &mut Formatter<'_>, note: &Note, german: bool) -> std::fmt::Result {
+    let name = name(note, german);
+    let octave = note.octave as i16 + octave_offset(note.alphabet);
+    if octave >= 3 {
+        write!(f, "{name}")?;
+        write_octave_marks(f, (octave - 3) as i8)
+    } else {
+        let mut chars = name.chars();
+        let first = chars.next().map(|c| c.to_ascii_uppercase());
+        write!(f, "{}{}", first.unwrap_or_default(), chars.as_str())?;
+        write_octave_marks(f, (octave - 2) as i8)
+    }
+}
+
+fn parse_helmholtz(s: &str, german: bool) -> Option<Note> {
+    let (body, marks) = parse_octave_marks(s.trim());
+    let first = body.chars().next()?;
+    let octave = if first.is_lowercase() {
+        3 + marks as i16
+    } else {
+        2 + marks as i16
+    };
+    let body = body.to_lowercase();
+    let (alphabet, accidental) = (0..7)
+        .map(Alphabet::from_index)
+        .flat_map(|alphabet| {
+            [
+                Accidental::DoubleFlat,
+                Accidental::Flat,
+                Accidental::Natural,
+                Accidental::Sharp,
+                Accidental::DoubleSharp,
+            ]
+            .map(|accidental| (alphabet, accidental))
+        })
+        .find(|(alphabet, accidental)| name(&Note::new(*alphabet, *accidental, 4), german) == body)
+        .or(match (german, body.as_str()) {
+            // Also common, though irregular.
+            (true, "bes") => Some((Alphabet::B, Accidental::DoubleFlat)),
+            (_, "ees") => Some((Alphabet::E, Accidental::Flat)),
+            (_, "aes") => Some((Alphabet::A, Accidental::Flat)),
+            _ => None,
+        })?;
+    let octave = u8::try_from(octave - octave_offset(alphabet)).ok()?;
+    Some(Note::new(alphabet, accidental, octave))
+}
+
+impl Display for GermanNote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_helmholtz(f, &self.0, true)
+    }
+}
+
+impl FromStr for GermanNote {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_helmholtz(s, true).map(Self).ok_or(())
+    }
+}
+
+impl Display for DutchNote {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_helmholtz(f, &self.0, false)
+    }
+}
+
+impl FromStr for DutchNote {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_helmholtz(s, false).map(Self).ok_or(())
+    }
+}
+
+impl From<Note> for GermanNote {
+    fn from(note: Note) -> Self {
+        Self(note)
+    }
+}
+
+impl From<GermanNote> for Note {
+    fn from(note: GermanNote) -> Self {
+        note.0
+    }
+}
+
+impl From<Note> for DutchNote {
+    fn from(note: Note) -> Self {
+        Self(note)
+    }
+}
+
+impl From<DutchNote> for Note {
+    fn from(note: DutchNote) -> Self {
+        note.0
+    }
+}
+
+impl RelativeSystem for GermanNote {
+    fn root() -> Self {
+        Self(Note::new(Alphabet::C, Accidental::Natural, 3))
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new(second.0.id().0 - first.0.id().0)
+    }
+}
+
+impl RelativeSystem for DutchNote {
+    fn root() -> Self {
+        Self(Note::new(Alphabet::C, Accidental::Natural, 3))
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new(second.0.id().0 - first.0.id().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        let cases = [
+            (Alphabet::B, Accidental::Natural, "h", "b"),
+            (Alphabet::B, Accidental::Flat, "b", "bes"),
+            (Alphabet::B, Accidental::DoubleFlat, "heses", "beses"),
+            (Alphabet::A, Accidental::Flat, "as", "as"),
+            (Alphabet::E, Accidental::DoubleFlat, "eses", "eses"),
+            (Alphabet::F, Accidental::Sharp, "fis", "fis"),
+            (Alphabet::C, Accidental::DoubleSharp, "cisis", "cisis"),
+            (Alphabet::D, Accidental::Flat, "des", "des"),
+        ];
+        for (alphabet, accidental, german, dutch) in cases {
+            // The octave below middle C.
+            let octave = if matches!(alphabet, Alphabet::A | Alphabet::B) {
+                3
+            } else {
+                2
+            };
+            let note = Note::new(alphabet, accidental, octave);
+            assert_eq!(GermanNote(note).to_string(), german);
+            assert_eq!(DutchNote(note).to_string(), dutch);
+            let parsed = german.parse::<GermanNote>().unwrap().0;
+            assert_eq!((parsed.alphabet, parsed.accidental), (alphabet, accidental));
+            let parsed = dutch.parse::<DutchNote>().unwrap().0;
+            assert_eq!((parsed.alphabet, parsed.accidental), (alphabet, accidental));
+        }
+    }
+
+    #[test]
+    fn test_octaves() {
+        for (octave, c_helmholtz, a_helmholtz) in [
+            (0, "C,", "A,,"),
+            (1, "C", "A,"),
+            (2, "c", "A"),
+            (3, "c'", "a"),
+            (4, "c''", "a'"),
+            (6, "c''''", "a'''"),
+        ] {
+            let c = GermanNote(Note::new(Alphabet::C, Accidental::Natural, octave));
+            assert_eq!(c.to_string(), c_helmholtz);
+            assert_eq!(c_helmholtz.parse::<GermanNote>().unwrap(), c);
+            let a = GermanNote(Note::new(Alphabet::A, Accidental::Natural, octave));
+            assert_eq!(a.to_string(), a_helmholtz);
+            assert_eq!(a_helmholtz.parse::<GermanNote>().unwrap(), a);
+        }
+        // Middle C is 9 half steps below A4, or MIDI note 60.
+        assert_eq!("c'".parse::<GermanNote>().unwrap().0.id().0, -9);
+        assert!("x".parse::<GermanNote>().is_err());
+        assert!("his".parse::<DutchNote>().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Numbered musical notation (jianpu), where the degrees of the major scale are written 1 to 7.
+
+use crate::note::Note;
+use crate::pitch::{RelativePitch, RelativeSystem};
+use crate::solfege::{
+    major_degree, octaves_above, parse_octave_marks, shift_octaves, spell_major_degree,
+    write_octave_marks, SolfegeSyllable,
+};
+use crate::{Accidental, Interval};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A note in numbered notation, relative to the key written as "1=D".
+///
+/// Octaves are written with `'` above and `,` below, in place of the dots of printed jianpu.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::jianpu::Jianpu;
+/// let root = Note::new(Alphabet::D, Accidental::Natural, 4);
+/// let jianpu = "#4'".parse::<Jianpu>().unwrap();
+/// let note = jianpu.to_note(&root).unwrap();
+/// assert_eq!(note, Note::new(Alphabet::G, Accidental::Sharp, 5));
+/// assert_eq!(Jianpu::from_note(&note, &root), jianpu);
+/// assert_eq!(jianpu.to_string(), "♯4'");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Jianpu {
+    /// The degree from 1 to 7.
+    pub degree: u8,
+    /// The alteration of the degree, where [`Accidental::None`] leaves it unaltered.
+    pub accidental: Accidental,
+    /// The number of octaves above the octave of the root.
+    pub octave: i8,
+}
+
+impl Jianpu {
+    pub const fn new(degree: u8, accidental: Accidental, octave: i8) -> Self {
+        Self {
+            degree,
+            accidental,
+            octave,
+        }
+    }
+
+    /// Finds the number of a note when 1 is `root`, following the spelling of the note.
+    pub fn from_note(note: &Note, root: &Note) -> Self {
+        let (degree, alteration) = major_degree(root, note);
+        let half_steps = SolfegeSyllable::DIATONIC[degree as usize].into_u8() as i16;
+        let accidental = match Accidental::from_offset(alteration) {
+            Some(Accidental::Natural) | None => Accidental::None,
+            Some(accidental) => accidental,
+        };
+        Self::new(
+            degree + 1,
+            accidental,
+            octaves_above(root, note, half_steps + alteration as i16),
+        )
+    }
+
+    /// Spells the number as a note when 1 is `root`, or `None` if the degree is not from 1 to 7.
+    pub fn to_note(&self, root: &Note) -> Option<Note> {
+        if !(1..=7).contains(&self.degree) {
+            return None;
+        }
+        let note = spell_major_degree(root, self.degree - 1, self.accidental.offset())?;
+        shift_octaves(note, self.octave)
+    }
+
+    /// The number of half steps above the root.
+    pub const fn half_steps(&self) -> i16 {
+        SolfegeSyllable::DIATONIC[(self.degree as usize + 6) % 7].into_u8() as i16
+            + self.accidental.offset() as i16
+            + self.octave as i16 * 12
+    }
+}
+
+impl RelativeSystem for Jianpu {
+    fn root() -> Self {
+        Self::new(1, Accidental::None, 0)
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new(second.half_steps() - first.half_steps())
+    }
+}
+
+impl Display for Jianpu {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.accidental.unicode(), self.degree)?;
+        write_octave_marks(f, self.octave)
+    }
+}
+
+impl FromStr for Jianpu {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, octave) = parse_octave_marks(s.trim());
+        let digit = body.chars().last().ok_or(())?;
+        let degree = digit
+            .to_digit(10)
+            .filter(|d| (1..=7).contains(d))
+            .ok_or(())? as u8;
+        let accidental = match &body[..body.len() - 1] {
+            "" => Accidental::None,
+            "#" | "♯" => Accidental::Sharp,
+            "b" | "♭" => Accidental::Flat,
+            "##" | "x" | "𝄪" => Accidental::DoubleSharp,
+            "bb" | "𝄫" => Accidental::DoubleFlat,
+            _ => return Err(()),
+        };
+        Ok(Self::new(degree, accidental, octave))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alphabet;
+
+    #[test]
+    fn test_jianpu() {
+        let root = Note::new(Alphabet::E, Accidental::Flat, 4);
+        let melody = ["5,", "1", "b3", "5", "1'", "7"]
+            .map(|jianpu| jianpu.parse::<Jianpu>().unwrap())
+            .map(|jianpu| jianpu.to_note(&root).unwrap());
+        let expected = [
+            Note::new(Alphabet::B, Accidental::Flat, 4),
+            Note::new(Alphabet::E, Accidental::Flat, 4),
+            Note::new(Alphabet::G, Accidental::Flat, 4),
+            Note::new(Alphabet::B, Accidental::Flat, 5),
+            Note::new(Alphabet::E, Accidental::Flat, 5),
+            Note::new(Alphabet::D, Accidental::Natural, 5),
+        ];
+        for (note, expected) in melody.iter().zip(expected) {
+            assert_eq!(note.alphabet, expected.alphabet);
+            assert_eq!(note.accidental, expected.accidental);
+            assert_eq!(note.id(), expected.id());
+        }
+        let five = Jianpu::from_note(&melody[0], &root);
+        let seven = Jianpu::from_note(&melody[5], &root);
+        assert_eq!(five.to_string(), "5,");
+        assert_eq!(Jianpu::interval(five, seven), Interval::new(16));
+        assert!("8".parse::<Jianpu>().is_err());
+        assert_eq!(Jianpu::new(0, Accidental::None, 0).to_note(&root), None);
+        assert_eq!(Jianpu::new(9, Accidental::None, 0).to_note(&root), None);
+    }
+}
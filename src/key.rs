@@ -1,6 +1,6 @@
 use crate::note::Note;
 use crate::prelude::Pitch;
-use crate::{Accidental, Alphabet, KeySignature};
+use crate::{Accidental, Alphabet, KeySignature, Tonality};
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -183,6 +183,32 @@ impl Key {
             _ => None,
         }
     }
+
+    /// Returns whether the key is major or minor.
+    ///
+    /// Only keys that match one built by [`Key::new_major`] or [`Key::new_minor`] have a tonality.
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_minor(Note::new(Alphabet::E, Accidental::Natural, 4)).unwrap();
+    /// assert_eq!(key.tonality(), Some(Tonality::Minor));
+    /// assert_eq!(Key::new_chromatic().tonality(), None);
+    /// ```
+    pub fn tonality(&self) -> Option<Tonality> {
+        match self {
+            Self::Chromatic => None,
+            Self::Diatonic { signature, root } => {
+                let matches = |key: Option<Self>| matches!(key, Some(Self::Diatonic { signature: s, .. }) if &s == signature);
+                if matches(Self::new_major(*root)) {
+                    Some(Tonality::Major)
+                } else if matches(Self::new_minor(*root)) {
+                    Some(Tonality::Minor)
+                } else {
+                    None
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -221,7 +247,7 @@ impl From<&Key> for Scale {
                     notes.push(note);
                     current_alphabet = current_alphabet.next();
                 }
-                debug_assert!(notes.contains(&root));
+                debug_assert!(notes.contains(root));
                 Self { notes }
             }
         }
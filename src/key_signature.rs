@@ -1,7 +1,7 @@
 use crate::alphabet::Alphabet;
 use crate::Accidental;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeySignature {
     pub accidental: Accidental,
@@ -1,22 +1,35 @@
 mod accidental;
 mod alphabet;
+pub mod analysis;
+pub mod beaming;
 pub mod chord;
 mod clef;
 pub mod duration;
+pub mod figured_bass;
+pub mod fretted;
+pub mod german;
 mod interval;
+pub mod jianpu;
 pub mod key;
 mod key_signature;
 pub mod measure;
+pub mod nashville;
+pub mod neo_riemannian;
 pub mod note;
 mod part;
 pub mod pitch;
+pub mod playback;
+#[cfg(feature = "rand")]
+pub mod progression;
 mod rest;
 pub mod roman_numeral;
+pub mod sargam;
 mod scale_degree;
 pub mod score;
 pub mod solfege;
 pub mod time;
 mod time_signature;
+pub mod tonic_sol_fa;
 mod tonality;
 
 pub use accidental::Accidental;
@@ -24,10 +37,10 @@ pub use alphabet::Alphabet;
 pub use clef::Clef;
 pub use interval::Interval;
 pub use key_signature::KeySignature;
-pub use part::Part;
+pub use part::{Part, Staff};
 pub use rest::Rest;
 pub use scale_degree::ScaleDegree;
-pub use time_signature::TimeSignature;
+pub use time_signature::{MetricWeight, TimeSignature, TimeSignatureSymbol};
 pub use tonality::Tonality;
 
 #[allow(unused_imports)]
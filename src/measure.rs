@@ -1,5 +1,6 @@
 use crate::chord::Chord;
-use crate::duration::Duration;
+use crate::duration::{Duration, Fraction};
+use crate::figured_bass::FiguredBass;
 use crate::key::Key;
 use crate::note::Note;
 use crate::{Clef, TimeSignature};
@@ -312,47 +313,692 @@ pub enum TimedMeasureItemInner {
     Barline,
     // TODO: implement these:
     // TODO: <harmony>
-    // TODO: <figured-bass>
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeModification {
-    pub actual_notes: u8,
-    pub normal_notes: u8,
+    pub actual_notes: u16,
+    pub normal_notes: u16,
+}
+
+/// The state of one level of beam on an item, as in MusicXML.
+/// The first level is the eighth note beam, the second the sixteenth note beam, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Beam {
+    Begin,
+    Continue,
+    End,
+    /// A partial beam pointing to the next item.
+    ForwardHook,
+    /// A partial beam pointing to the previous item.
+    BackwardHook,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimedMeasureItem {
-    item: TimedMeasureItemInner,
-    duration: Duration,
+    pub(crate) item: TimedMeasureItemInner,
+    pub(crate) duration: Duration,
     position: Option<Position>,
     /// Up or down
     stem: Option<Placement>,
     staff: Option<u8>,
-    voice: Option<u8>,
-    beam_id: Option<u16>,
-    time_modification: Option<TimeModification>,
+    pub(crate) voice: Option<u8>,
+    pub(crate) beam_id: Option<u16>,
+    /// The beams at each level, starting with the eighth note beam.
+    pub(crate) beams: Vec<Beam>,
     notations: Option<Notations>,
     /// Delay of the note
     attack: Option<f32>,
+    /// Figures written under a bass note or chord.
+    figures: Option<FiguredBass>,
     // TODO: lyrics
     // TODO: notehead
     // TODO: notehead-text
     // TODO: grace notes
 }
 
+impl TimedMeasureItem {
+    /// Creates an item with the given duration and no other attributes.
+    pub const fn new(item: TimedMeasureItemInner, duration: Duration) -> Self {
+        Self {
+            item,
+            duration,
+            position: None,
+            stem: None,
+            staff: None,
+            voice: None,
+            beam_id: None,
+            beams: vec![],
+            notations: None,
+            attack: None,
+            figures: None,
+        }
+    }
+
+    /// Creates a single note.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let note = Note::new(Alphabet::C, Accidental::Natural, 4);
+    /// let item = TimedMeasureItem::note(note, Duration::QUARTER).with_voice(2);
+    /// assert_eq!(item.item(), &TimedMeasureItemInner::Note(note));
+    /// assert_eq!(item.voice(), Some(2));
+    /// ```
+    #[inline]
+    pub const fn note(note: Note, duration: Duration) -> Self {
+        Self::new(TimedMeasureItemInner::Note(note), duration)
+    }
+
+    /// Creates notes played together.
+    #[inline]
+    pub const fn chord(chord: Chord, duration: Duration) -> Self {
+        Self::new(TimedMeasureItemInner::Chord(chord), duration)
+    }
+
+    #[inline]
+    pub const fn rest(duration: Duration) -> Self {
+        Self::new(TimedMeasureItemInner::Rest, duration)
+    }
+
+    /// Moves the position in the measure forward without writing anything, as in MusicXML.
+    #[inline]
+    pub const fn forward(duration: Duration) -> Self {
+        Self::new(TimedMeasureItemInner::Forward, duration)
+    }
+
+    /// Moves the position in the measure back, so another voice can be written, as in MusicXML.
+    #[inline]
+    pub const fn backward(duration: Duration) -> Self {
+        Self::new(TimedMeasureItemInner::Backward, duration)
+    }
+
+    #[inline]
+    pub const fn item(&self) -> &TimedMeasureItemInner {
+        &self.item
+    }
+
+    #[inline]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    #[inline]
+    pub const fn position(&self) -> Option<&Position> {
+        self.position.as_ref()
+    }
+
+    /// The direction of the stem.
+    #[inline]
+    pub const fn stem(&self) -> Option<&Placement> {
+        self.stem.as_ref()
+    }
+
+    #[inline]
+    pub const fn staff(&self) -> Option<u8> {
+        self.staff
+    }
+
+    #[inline]
+    pub const fn voice(&self) -> Option<u8> {
+        self.voice
+    }
+
+    #[inline]
+    pub const fn notations(&self) -> Option<&Notations> {
+        self.notations.as_ref()
+    }
+
+    /// The delay of the note.
+    #[inline]
+    pub const fn attack(&self) -> Option<f32> {
+        self.attack
+    }
+
+    /// The figures written under the item, if it is a bass note or chord.
+    #[inline]
+    pub const fn figures(&self) -> Option<&FiguredBass> {
+        self.figures.as_ref()
+    }
+
+    /// The beam the item belongs to, shared by every item under the same beam.
+    pub const fn beam_id(&self) -> Option<u16> {
+        self.beam_id
+    }
+
+    /// The beams at each level, starting with the eighth note beam.
+    pub fn beams(&self) -> &[Beam] {
+        &self.beams
+    }
+
+    /// The tuplet ratio of the item, taken from its duration.
+    pub fn time_modification(&self) -> Option<TimeModification> {
+        self.duration.tuplet.map(|tuplet| TimeModification {
+            actual_notes: tuplet.actual_notes,
+            normal_notes: tuplet.normal_notes,
+        })
+    }
+
+    pub fn with_duration(self, duration: Duration) -> Self {
+        Self { duration, ..self }
+    }
+
+    pub fn with_position(self, position: Position) -> Self {
+        Self {
+            position: Some(position),
+            ..self
+        }
+    }
+
+    pub fn with_stem(self, stem: Placement) -> Self {
+        Self {
+            stem: Some(stem),
+            ..self
+        }
+    }
+
+    pub fn with_staff(self, staff: u8) -> Self {
+        Self {
+            staff: Some(staff),
+            ..self
+        }
+    }
+
+    pub fn with_voice(self, voice: u8) -> Self {
+        Self {
+            voice: Some(voice),
+            ..self
+        }
+    }
+
+    /// Adds a notation, keeping any the item already has.
+    pub fn with_notation(self, notation: Notation) -> Self {
+        let mut notations = self.notations.unwrap_or(Notations { notations: vec![] });
+        notations.notations.push(notation);
+        Self {
+            notations: Some(notations),
+            ..self
+        }
+    }
+
+    pub fn with_attack(self, attack: f32) -> Self {
+        Self {
+            attack: Some(attack),
+            ..self
+        }
+    }
+
+    pub fn with_figures(self, figures: FiguredBass) -> Self {
+        Self {
+            figures: Some(figures),
+            ..self
+        }
+    }
+}
+
+/// An item placed in time by [`Measure::voices`] or [`crate::Part::voices`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceItem<'a> {
+    /// The index of the measure the item is in.
+    pub measure: usize,
+    /// The time the item starts, from the start of the measure or part it was resolved in.
+    pub onset: Fraction,
+    /// The staff the item is written on, counting from 1.
+    pub staff: u8,
+    pub item: &'a TimedMeasureItem,
+}
+
+impl VoiceItem<'_> {
+    #[inline]
+    pub fn end(&self) -> Fraction {
+        self.onset + self.item.duration.length()
+    }
+}
+
+/// The notes, chords and rests of one voice, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Voice<'a> {
+    /// The number of the voice, counting from 1.
+    pub number: u8,
+    pub items: Vec<VoiceItem<'a>>,
+}
+
+/// Whether a measure is expected to fill its time signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeasureKind {
+    #[default]
+    Complete,
+    /// An anacrusis before the first full measure, which is shorter than the time signature.
+    Pickup,
+    /// A measure cut short, like the last measure of a piece that starts with a pickup.
+    Incomplete,
+}
+
+/// A voice whose contents do not match the length of its measure.
+///
+/// Voices are numbered as in [`Measure::voices`], so items without a voice are in voice 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeasureError {
+    Underfull { voice: u8, missing: Fraction },
+    Overfull { voice: u8, excess: Fraction },
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Measure {
     pub clef: Clef,
     pub time_signature: TimeSignature,
     pub key: Key,
-    pub notes: TimedMeasureItem,
+    /// The contents of the measure, in order.
+    pub notes: Vec<TimedMeasureItem>,
     pub directions: Vec<Direction>,
+    pub kind: MeasureKind,
+}
+
+impl Measure {
+    /// Creates an empty measure.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let mut measure = Measure::new(Clef::Treble, TimeSignature::new(2, 4), key);
+    /// let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+    /// let e = Note::new(Alphabet::E, Accidental::Natural, 4);
+    /// measure.push(TimedMeasureItem::note(c, Duration::QUARTER));
+    /// measure.push(TimedMeasureItem::rest(Duration::QUARTER));
+    /// let rest = measure.replace(1, TimedMeasureItem::note(e, Duration::QUARTER));
+    /// assert_eq!(rest.item(), &TimedMeasureItemInner::Rest);
+    /// assert_eq!(measure.notes.len(), 2);
+    /// ```
+    pub fn new(clef: Clef, time_signature: TimeSignature, key: Key) -> Self {
+        Self {
+            clef,
+            time_signature,
+            key,
+            notes: vec![],
+            directions: vec![],
+            kind: MeasureKind::Complete,
+        }
+    }
+
+    /// Creates an empty pickup measure.
+    pub fn pickup(clef: Clef, time_signature: TimeSignature, key: Key) -> Self {
+        Self {
+            kind: MeasureKind::Pickup,
+            ..Self::new(clef, time_signature, key)
+        }
+    }
+
+    /// Places the notes, chords and rests of the measure in their voices,
+    /// moving a cursor through the measure as in MusicXML.
+    ///
+    /// Each item starts at the cursor and moves it forward by its duration.
+    /// Forwards move the cursor forward without an item, and backwards move it back,
+    /// so that another voice can be written over the same time.
+    /// Items without a voice or staff are in voice 1 on staff 1.
+    /// The voices are ordered by number, and the items of each voice by onset.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+    /// let mut measure = Measure::new(Clef::Treble, TimeSignature::new(2, 4), key);
+    /// measure.push(TimedMeasureItem::note(c, Duration::HALF));
+    /// measure.push(TimedMeasureItem::backward(Duration::HALF));
+    /// measure.push(TimedMeasureItem::forward(Duration::QUARTER).with_voice(2));
+    /// measure.push(TimedMeasureItem::note(c, Duration::QUARTER).with_voice(2).with_staff(2));
+    /// let voices = measure.voices();
+    /// assert_eq!(voices.len(), 2);
+    /// assert_eq!(voices[1].items[0].onset, Fraction::new(1, 4));
+    /// assert_eq!(voices[1].items[0].staff, 2);
+    /// ```
+    pub fn voices(&self) -> Vec<Voice<'_>> {
+        let mut voices: Vec<Voice> = vec![];
+        for (_, onset, item) in self.onsets() {
+            if item.item == TimedMeasureItemInner::Forward {
+                continue;
+            }
+            let number = item.voice.unwrap_or(1);
+            let resolved = VoiceItem {
+                measure: 0,
+                onset,
+                staff: item.staff.unwrap_or(1),
+                item,
+            };
+            match voices.iter_mut().find(|voice| voice.number == number) {
+                Some(voice) => voice.items.push(resolved),
+                None => voices.push(Voice {
+                    number,
+                    items: vec![resolved],
+                }),
+            }
+        }
+        voices.sort_by_key(|voice| voice.number);
+        for voice in voices.iter_mut() {
+            voice.items.sort_by_key(|item| item.onset);
+        }
+        voices
+    }
+
+    /// The length the measure takes up: the time signature for a complete measure,
+    /// or the end of the last item in free time, pickups and incomplete measures.
+    pub fn length(&self) -> Fraction {
+        if self.kind == MeasureKind::Complete && !self.time_signature.is_free() {
+            return self.time_signature.measure_length();
+        }
+        self.onsets()
+            .map(|(_, onset, item)| onset + item.duration.length())
+            .max()
+            .unwrap_or(Fraction::ZERO)
+    }
+
+    /// The index and onset of every note, chord, rest and forward,
+    /// with the cursor moved as in [`Measure::voices`].
+    fn onsets(&self) -> impl Iterator<Item = (usize, Fraction, &TimedMeasureItem)> {
+        let mut cursor = Fraction::ZERO;
+        self.notes
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, item)| {
+                let length = item.duration.length();
+                match item.item {
+                    TimedMeasureItemInner::Backward => {
+                        cursor = cursor.checked_sub(length).unwrap_or(Fraction::ZERO);
+                        None
+                    }
+                    TimedMeasureItemInner::Barline => None,
+                    _ => {
+                        let onset = cursor;
+                        cursor = cursor + length;
+                        Some((index, onset, item))
+                    }
+                }
+            })
+    }
+
+    /// The index of the item that ends each voice, and where it ends,
+    /// in the order the voices first appear.
+    fn voice_ends(&self) -> Vec<(u8, usize, Fraction)> {
+        let mut ends: Vec<(u8, usize, Fraction)> = vec![];
+        for (index, onset, item) in self.onsets() {
+            let number = item.voice.unwrap_or(1);
+            let end = onset + item.duration.length();
+            match ends.iter_mut().find(|(voice, _, _)| *voice == number) {
+                Some(last) if last.2 <= end => *last = (number, index, end),
+                Some(_) => {}
+                None => ends.push((number, index, end)),
+            }
+        }
+        ends
+    }
+
+    /// The end of each voice, in the order the voices first appear.
+    ///
+    /// A voice ends where its last note, chord, rest or forward ends,
+    /// with backwards and forwards moving the cursor as in [`Measure::voices`].
+    /// Items without a voice are in voice 1, and a measure without items has an empty voice 1.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+    /// let mut measure = Measure::new(Clef::Treble, TimeSignature::new(4, 4), key);
+    /// measure.push(TimedMeasureItem::note(c, Duration::WHOLE));
+    /// measure.push(TimedMeasureItem::backward(Duration::HALF));
+    /// measure.push(TimedMeasureItem::note(c, Duration::WHOLE).with_voice(2));
+    /// assert_eq!(measure.voice_lengths(), [(1, Fraction::new(1, 1)), (2, Fraction::new(3, 2))]);
+    /// ```
+    pub fn voice_lengths(&self) -> Vec<(u8, Fraction)> {
+        let mut lengths = self
+            .voice_ends()
+            .into_iter()
+            .map(|(voice, _, end)| (voice, end))
+            .collect::<Vec<_>>();
+        if lengths.is_empty() {
+            lengths.push((1, Fraction::ZERO));
+        }
+        lengths
+    }
+
+    /// Checks that every voice fills the time signature.
+    ///
+    /// Pickup and incomplete measures may be shorter, and measures in free time may be any length.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// use note_pen::measure::MeasureError;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let mut measure = Measure::new(Clef::Treble, TimeSignature::new(3, 4), key);
+    /// measure.push(TimedMeasureItem::rest(Duration::HALF));
+    /// let missing = Fraction::new(1, 4);
+    /// assert_eq!(measure.validate(), Err(vec![MeasureError::Underfull { voice: 1, missing }]));
+    /// assert!(measure.fill_rests());
+    /// assert_eq!(measure.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<MeasureError>> {
+        if self.time_signature.is_free() {
+            return Ok(());
+        }
+        let expected = self.time_signature.measure_length();
+        let errors = self
+            .voice_lengths()
+            .into_iter()
+            .filter_map(|(voice, length)| match expected.checked_sub(length) {
+                None => Some(MeasureError::Overfull {
+                    voice,
+                    excess: length - expected,
+                }),
+                Some(missing) if !missing.is_zero() && self.kind == MeasureKind::Complete => {
+                    Some(MeasureError::Underfull { voice, missing })
+                }
+                Some(_) => None,
+            })
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Fills the end of every underfull voice of a complete measure with rests,
+    /// grouped by the time signature with [`TimeSignature::split`],
+    /// so that the measure passes [`Measure::validate`].
+    /// The rests go right after the item that ends the voice,
+    /// followed by backwards over them if other items come later, so no other item moves.
+    /// Returns `false` and leaves the measure unchanged if a voice is overfull,
+    /// which rests cannot fix, or if a gap cannot be written without tuplets.
+    pub fn fill_rests(&mut self) -> bool {
+        let Err(errors) = self.validate() else {
+            return true;
+        };
+        let mut fills = vec![];
+        for error in errors {
+            let MeasureError::Underfull { voice, missing } = error else {
+                return false;
+            };
+            let length = self.time_signature.measure_length() - missing;
+            let Some(rests) = self.time_signature.split(length, missing) else {
+                return false;
+            };
+            fills.push((voice, rests));
+        }
+        for (voice, rests) in fills {
+            // Rests and backwards take up no time together, so the ends of other voices stay put.
+            let last = self
+                .voice_ends()
+                .into_iter()
+                .find(|(number, _, _)| *number == voice)
+                .map(|(_, index, _)| index);
+            let (voice, staff) = match last {
+                Some(index) => (self.notes[index].voice, self.notes[index].staff),
+                None => (Some(voice), None),
+            };
+            let index = last.map_or(0, |index| index + 1);
+            let backwards = if index < self.notes.len() {
+                rests.clone()
+            } else {
+                vec![]
+            };
+            let rests = rests.into_iter().map(|duration| TimedMeasureItem {
+                voice,
+                staff,
+                ..TimedMeasureItem::rest(duration)
+            });
+            let items = rests
+                .chain(backwards.into_iter().map(TimedMeasureItem::backward))
+                .collect::<Vec<_>>();
+            self.notes.splice(index..index, items);
+        }
+        true
+    }
+
+    /// Adds an item to the end of the measure.
+    #[inline]
+    pub fn push(&mut self, item: TimedMeasureItem) {
+        self.notes.push(item);
+    }
+
+    /// Inserts an item before the item at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of items.
+    #[inline]
+    pub fn insert(&mut self, index: usize, item: TimedMeasureItem) {
+        self.notes.insert(index, item);
+    }
+
+    /// Removes and returns the item at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> TimedMeasureItem {
+        self.notes.remove(index)
+    }
+
+    /// Replaces the item at `index`, returning the old item.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn replace(&mut self, index: usize, item: TimedMeasureItem) -> TimedMeasureItem {
+        std::mem::replace(&mut self.notes[index], item)
+    }
 }
 
 #[cfg(feature = "musicxml")]
 mod musicxml {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_validate() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::COMMON_TIME, key.clone());
+        measure.push(TimedMeasureItem::note(c, Duration::EIGHTH));
+        measure.push(TimedMeasureItem::backward(Duration::EIGHTH));
+        measure.push(TimedMeasureItem::note(c, Duration::WHOLE).with_voice(2));
+        measure.push(TimedMeasureItem::note(c, Duration::QUARTER).with_voice(2));
+        assert_eq!(
+            measure.validate(),
+            Err(vec![
+                MeasureError::Underfull {
+                    voice: 1,
+                    missing: Fraction::new(7, 8),
+                },
+                MeasureError::Overfull {
+                    voice: 2,
+                    excess: Fraction::new(1, 4),
+                },
+            ])
+        );
+        // Voice 2 is too long, so no rests are added.
+        assert!(!measure.fill_rests());
+        assert_eq!(measure.notes.len(), 4);
+        measure.remove(3);
+        assert!(measure.fill_rests());
+        assert_eq!(measure.validate(), Ok(()));
+        // An eighth, then rests up to beat 2, beat 2 and the second half of the measure.
+        let rests = measure.notes[1..4]
+            .iter()
+            .map(|item| item.duration())
+            .collect::<Vec<_>>();
+        assert_eq!(rests, [Duration::EIGHTH, Duration::QUARTER, Duration::HALF]);
+        // Backwards over the rests keep voice 2 at the start of the measure.
+        assert_eq!(measure.notes[4].item(), &TimedMeasureItemInner::Backward);
+        assert_eq!(measure.voices()[1].items[0].onset, Fraction::ZERO);
+        let mut pickup = Measure::pickup(Clef::Treble, TimeSignature::COMMON_TIME, key);
+        pickup.push(TimedMeasureItem::note(c, Duration::QUARTER));
+        assert_eq!(pickup.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_moved_voices() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+        // A backward that does not reach the start of the measure pushes voice 2 past the barline.
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::COMMON_TIME, key.clone());
+        measure.push(TimedMeasureItem::note(c, Duration::WHOLE));
+        measure.push(TimedMeasureItem::backward(Duration::HALF));
+        measure.push(TimedMeasureItem::note(c, Duration::WHOLE).with_voice(2));
+        assert_eq!(
+            measure.validate(),
+            Err(vec![MeasureError::Overfull {
+                voice: 2,
+                excess: Fraction::new(1, 2),
+            }])
+        );
+        assert!(!measure.fill_rests());
+
+        // The rests for voice 1 go before the backward without moving voice 2.
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::COMMON_TIME, key);
+        measure.push(TimedMeasureItem::note(c, Duration::HALF));
+        measure.push(TimedMeasureItem::backward(Duration::HALF));
+        measure.push(TimedMeasureItem::note(c, Duration::WHOLE).with_voice(2));
+        assert!(measure.fill_rests());
+        assert_eq!(measure.validate(), Ok(()));
+        let voices = measure.voices();
+        let onsets = |voice: usize| {
+            voices[voice]
+                .items
+                .iter()
+                .map(|item| item.onset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(onsets(0), [Fraction::ZERO, Fraction::new(1, 2)]);
+        assert_eq!(onsets(1), [Fraction::ZERO]);
+    }
+
+    #[test]
+    fn test_default_voice() {
+        // Items without a voice are in voice 1, like items with voice 1.
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::new(2, 4), key);
+        measure.push(TimedMeasureItem::note(c, Duration::QUARTER));
+        measure.push(TimedMeasureItem::note(c, Duration::QUARTER).with_voice(1));
+        assert_eq!(measure.voices().len(), 1);
+        assert_eq!(measure.voice_lengths(), [(1, Fraction::new(1, 2))]);
+        assert_eq!(measure.validate(), Ok(()));
+        assert!(measure.fill_rests());
+        assert_eq!(measure.notes.len(), 2);
+    }
+}
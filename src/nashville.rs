@@ -0,0 +1,311 @@
+//! The Nashville number system, where chords are written as degrees of the major scale of the key.
+
+use crate::chord::Chord;
+use crate::note::Note;
+use crate::pitch::{RelativePitch, RelativeSystem};
+use crate::solfege::{major_degree, spell_major_degree, SolfegeSyllable};
+use crate::{Accidental, Interval, Tonality};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A degree of the major scale, like 4 or ♭7.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NashvilleDegree {
+    /// The degree from 1 to 7.
+    pub degree: u8,
+    /// The alteration of the degree, where [`Accidental::None`] leaves it unaltered.
+    pub accidental: Accidental,
+}
+
+impl NashvilleDegree {
+    pub const fn new(degree: u8, accidental: Accidental) -> Self {
+        Self { degree, accidental }
+    }
+
+    /// Finds the degree of a note in the major key of `tonic`, following the spelling of the note.
+    pub fn from_note(note: &Note, tonic: &Note) -> Self {
+        let (degree, alteration) = major_degree(tonic, note);
+        let accidental = match Accidental::from_offset(alteration) {
+            Some(Accidental::Natural) | None => Accidental::None,
+            Some(accidental) => accidental,
+        };
+        Self::new(degree + 1, accidental)
+    }
+
+    /// Spells the degree in the major key of `tonic`, in the octave above the tonic,
+    /// or `None` if the degree is not from 1 to 7.
+    pub fn to_note(&self, tonic: &Note) -> Option<Note> {
+        if !(1..=7).contains(&self.degree) {
+            return None;
+        }
+        spell_major_degree(tonic, self.degree - 1, self.accidental.offset())
+    }
+
+    /// The number of half steps above the tonic, between -2 and 13.
+    pub const fn half_steps(&self) -> i16 {
+        SolfegeSyllable::DIATONIC[(self.degree as usize + 6) % 7].into_u8() as i16
+            + self.accidental.offset() as i16
+    }
+}
+
+impl RelativeSystem for NashvilleDegree {
+    fn root() -> Self {
+        Self::new(1, Accidental::None)
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new((second.half_steps() - first.half_steps()).rem_euclid(12))
+    }
+}
+
+impl Display for NashvilleDegree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let accidental = match self.accidental {
+            Accidental::DoubleFlat => "bb",
+            Accidental::Flat => "b",
+            Accidental::Sharp => "#",
+            Accidental::DoubleSharp => "##",
+            Accidental::None | Accidental::Natural => "",
+        };
+        write!(f, "{}{}", accidental, self.degree)
+    }
+}
+
+impl FromStr for NashvilleDegree {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digit = s.find(|c: char| c.is_ascii_digit()).ok_or(())?;
+        if s.len() != digit + 1 {
+            return Err(());
+        }
+        let degree = s[digit..].parse::<u8>().map_err(|_| ())?;
+        if !(1..=7).contains(&degree) {
+            return Err(());
+        }
+        let accidental = match &s[..digit] {
+            "" => Accidental::None,
+            "b" | "♭" => Accidental::Flat,
+            "#" | "♯" => Accidental::Sharp,
+            "bb" | "𝄫" => Accidental::DoubleFlat,
+            "##" | "𝄪" => Accidental::DoubleSharp,
+            _ => return Err(()),
+        };
+        Ok(Self::new(degree, accidental))
+    }
+}
+
+/// A chord chart entry like 1, 6-, 2-7, 4maj7 or 5/7.
+///
+/// Qualities are written `-` for minor, `°` for diminished, `ø` for half diminished and `+` for augmented,
+/// and `m`, `o` and `%` are accepted when parsing.
+/// A `7` adds a minor seventh, or a diminished seventh on a diminished chord, while `maj7` or `Δ7` adds a major seventh.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::nashville::NashvilleChord;
+/// let tonic = Note::new(Alphabet::G, Accidental::Natural, 4);
+/// let chord = "2-7/4".parse::<NashvilleChord>().unwrap().chord(&tonic).unwrap();
+/// let names = chord
+///     .notes
+///     .iter()
+///     .map(|note| note.alphabet)
+///     .collect::<Vec<_>>();
+/// assert_eq!(names, [Alphabet::C, Alphabet::A, Alphabet::E, Alphabet::G]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NashvilleChord {
+    pub root: NashvilleDegree,
+    pub quality: Tonality,
+    /// The seventh above the root, if any.
+    pub seventh: Option<Interval>,
+    /// The bass note of a slash chord.
+    pub bass: Option<NashvilleDegree>,
+}
+
+impl NashvilleChord {
+    pub const fn new(root: NashvilleDegree, quality: Tonality) -> Self {
+        Self {
+            root,
+            quality,
+            seventh: None,
+            bass: None,
+        }
+    }
+
+    /// Spells the chord in the major key of `tonic`, from the bass up.
+    pub fn chord(&self, tonic: &Note) -> Option<Chord> {
+        let root = self.root.to_note(tonic)?;
+        let (third, fifth) = match self.quality {
+            Tonality::Major => (Interval::MAJOR_THIRD, Interval::PERFECT_FIFTH),
+            Tonality::Minor => (Interval::MINOR_THIRD, Interval::PERFECT_FIFTH),
+            Tonality::Diminished | Tonality::HalfDiminished => {
+                (Interval::MINOR_THIRD, Interval::TRITONE)
+            }
+            Tonality::Augmented => (Interval::MAJOR_THIRD, Interval::AUGMENTED_FIFTH),
+        };
+        let above = |interval: Interval, steps: i64| {
+            root.increment_by(interval.0 as i64)
+                .respell(root.alphabet.shift(steps))
+        };
+        let mut notes = vec![root, above(third, 2)?, above(fifth, 4)?];
+        if let Some(seventh) = self.seventh {
+            notes.push(above(seventh, 6)?);
+        }
+        for note in notes.iter_mut() {
+            while note.id() < root.id() {
+                note.octave += 1;
+            }
+        }
+        if let Some(bass) = self.bass {
+            let mut bass = bass.to_note(tonic)?;
+            while bass.id() >= root.id() {
+                bass.octave = bass.octave.checked_sub(1)?;
+            }
+            notes.retain(|note| note.id().simple() != bass.id().simple());
+            notes.insert(0, bass);
+        }
+        Some(Chord::new(notes))
+    }
+}
+
+impl RelativeSystem for NashvilleChord {
+    fn root() -> Self {
+        Self::new(NashvilleDegree::root(), Tonality::Major)
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        NashvilleDegree::interval(first.root, second.root)
+    }
+}
+
+impl Display for NashvilleChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.root)?;
+        match self.quality {
+            Tonality::Major => {}
+            Tonality::Minor => write!(f, "-")?,
+            Tonality::Diminished => write!(f, "°")?,
+            Tonality::HalfDiminished => write!(f, "ø")?,
+            Tonality::Augmented => write!(f, "+")?,
+        }
+        match self.seventh {
+            Some(Interval::MAJOR_SEVENTH) => write!(f, "maj7")?,
+            Some(_) => write!(f, "7")?,
+            None => {}
+        }
+        if let Some(bass) = self.bass {
+            write!(f, "/{}", bass)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NashvilleChord {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, bass) = match s.trim().split_once('/') {
+            Some((s, bass)) => (s, Some(bass.parse::<NashvilleDegree>()?)),
+            None => (s.trim(), None),
+        };
+        let digit = s.find(|c: char| c.is_ascii_digit()).ok_or(())?;
+        let root = s[..=digit].parse::<NashvilleDegree>()?;
+        let mut rest = &s[digit + 1..];
+        let mut quality = Tonality::Major;
+        for (prefix, tonality) in [
+            ("-", Tonality::Minor),
+            ("m", Tonality::Minor),
+            ("°", Tonality::Diminished),
+            ("o", Tonality::Diminished),
+            ("ø", Tonality::HalfDiminished),
+            ("%", Tonality::HalfDiminished),
+            ("+", Tonality::Augmented),
+        ] {
+            // "maj7" starts with "m" but is a seventh, not a quality.
+            if rest.starts_with(prefix) && !rest.starts_with("maj") {
+                quality = tonality;
+                rest = &rest[prefix.len()..];
+                break;
+            }
+        }
+        let seventh = match rest {
+            "" if quality == Tonality::HalfDiminished => Some(Interval::MINOR_SEVENTH),
+            "" => None,
+            "7" if quality == Tonality::Diminished => Some(Interval::MAJOR_SIXTH),
+            "7" => Some(Interval::MINOR_SEVENTH),
+            "maj7" | "Δ7" | "Δ" => Some(Interval::MAJOR_SEVENTH),
+            _ => return Err(()),
+        };
+        Ok(Self {
+            root,
+            quality,
+            seventh,
+            bass,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alphabet;
+
+    #[test]
+    fn test_round_trip() {
+        for chart in ["1", "4/1", "6-", "2-7", "b7", "5+", "7ø7", "#4°7", "1maj7"] {
+            let chord = chart.parse::<NashvilleChord>().unwrap();
+            assert_eq!(chord.to_string(), chart);
+        }
+        assert_eq!("6m".parse::<NashvilleChord>().unwrap().to_string(), "6-");
+        assert!("8".parse::<NashvilleChord>().is_err());
+        assert!("1sus".parse::<NashvilleChord>().is_err());
+    }
+
+    #[test]
+    fn test_notes() {
+        let tonic = Note::new(Alphabet::E, Accidental::Natural, 4);
+        let degree =
+            NashvilleDegree::from_note(&Note::new(Alphabet::D, Accidental::Natural, 5), &tonic);
+        assert_eq!(degree.to_string(), "b7");
+        let chord = "b7"
+            .parse::<NashvilleChord>()
+            .unwrap()
+            .chord(&tonic)
+            .unwrap();
+        let expected = [
+            (Alphabet::D, Accidental::Natural),
+            (Alphabet::F, Accidental::Sharp),
+            (Alphabet::A, Accidental::Natural),
+        ];
+        for (note, (alphabet, accidental)) in chord.notes.iter().zip(expected) {
+            assert_eq!(note.alphabet, alphabet);
+            assert_eq!(note.accidental, accidental);
+        }
+        let dominant = "5".parse::<NashvilleChord>().unwrap();
+        let tonic_chord = "1".parse::<NashvilleChord>().unwrap();
+        assert_eq!(
+            NashvilleChord::interval(dominant, tonic_chord),
+            Interval::PERFECT_FOURTH
+        );
+        assert_eq!(
+            NashvilleDegree::new(0, Accidental::None).to_note(&tonic),
+            None
+        );
+        assert_eq!(
+            NashvilleDegree::new(8, Accidental::None).to_note(&tonic),
+            None
+        );
+    }
+}
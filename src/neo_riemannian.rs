@@ -0,0 +1,419 @@
+//! Neo-Riemannian transformations between major and minor triads, and their place on the Tonnetz.
+
+use crate::chord::{Chord, Inversion};
+use crate::note::Note;
+use crate::pitch::RelativePitch;
+use crate::{Interval, Tonality};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+
+/// One of the three basic neo-Riemannian transformations.
+///
+/// Each transformation keeps two notes of a triad and moves the third by a step,
+/// turning a major triad into a minor triad and vice versa.
+/// Every transformation is its own inverse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Transformation {
+    /// Parallel: C major ↔ C minor.
+    P,
+    /// Leading-tone exchange: C major ↔ E minor.
+    L,
+    /// Relative: C major ↔ A minor.
+    R,
+}
+
+impl Transformation {
+    pub const ALL: [Self; 3] = [Self::P, Self::L, Self::R];
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'P' | 'p' => Some(Self::P),
+            'L' | 'l' => Some(Self::L),
+            'R' | 'r' => Some(Self::R),
+            _ => None,
+        }
+    }
+
+    /// Parses a compound transformation such as `"PLR"`, ignoring whitespace.
+    ///
+    /// The transformations are applied from left to right.
+    /// # Examples
+    /// ```rust
+    /// use note_pen::neo_riemannian::Transformation;
+    /// assert_eq!(
+    ///     Transformation::parse_sequence("PLR"),
+    ///     Some(vec![Transformation::P, Transformation::L, Transformation::R])
+    /// );
+    /// assert!(Transformation::parse_sequence("PXR").is_none());
+    /// ```
+    pub fn parse_sequence(s: &str) -> Option<Vec<Self>> {
+        s.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(Self::from_char)
+            .collect()
+    }
+}
+
+impl Display for Transformation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::P => write!(f, "P"),
+            Self::L => write!(f, "L"),
+            Self::R => write!(f, "R"),
+        }
+    }
+}
+
+/// A major or minor triad, identified by its root and tonality.
+///
+/// Two triads are equal if their roots are the same pitch class and they have the same tonality,
+/// so the octave and spelling of the root are ignored.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triad {
+    pub root: Note,
+    pub tonality: Tonality,
+}
+
+impl Triad {
+    /// Creates a new triad, returning `None` unless the tonality is major or minor.
+    pub fn new(root: Note, tonality: Tonality) -> Option<Self> {
+        match tonality {
+            Tonality::Major | Tonality::Minor => Some(Self { root, tonality }),
+            Tonality::Diminished | Tonality::Augmented | Tonality::HalfDiminished => None,
+        }
+    }
+
+    /// Finds the major or minor triad formed by a chord in any inversion,
+    /// such as one built by [`Chord::triad_from_root`].
+    ///
+    /// Doubled notes are allowed, but any other note makes this return `None`.
+    pub fn from_chord(chord: &Chord) -> Option<Self> {
+        let pitches = chord
+            .notes
+            .iter()
+            .map(|note| note.id().simple())
+            .collect::<HashSet<_>>();
+        if pitches.len() != 3 {
+            return None;
+        }
+        chord.notes.iter().find_map(|root| {
+            [Tonality::Major, Tonality::Minor]
+                .into_iter()
+                .find(|&tonality| {
+                    Self {
+                        root: *root,
+                        tonality,
+                    }
+                    .pitches()
+                    .iter()
+                    .all(|pitch| pitches.contains(pitch))
+                })
+                .map(|tonality| Self {
+                    root: *root,
+                    tonality,
+                })
+        })
+    }
+
+    fn third(&self) -> Interval {
+        match self.tonality {
+            Tonality::Minor => Interval::MINOR_THIRD,
+            _ => Interval::MAJOR_THIRD,
+        }
+    }
+
+    fn pitches(&self) -> [RelativePitch; 3] {
+        let root = self.root.id();
+        [
+            root.simple(),
+            (self.root + self.third()).id().simple(),
+            (self.root + Interval::PERFECT_FIFTH).id().simple(),
+        ]
+    }
+
+    /// Builds the triad as a chord, spelling the third and fifth on the letters above the root.
+    pub fn chord(&self, inversion: Inversion) -> Chord {
+        let third = self.root + self.third();
+        let fifth = self.root + Interval::PERFECT_FIFTH;
+        let notes = vec![
+            self.root,
+            third.respell(self.root.alphabet.shift(2)).unwrap_or(third),
+            fifth.respell(self.root.alphabet.shift(4)).unwrap_or(fifth),
+        ];
+        Chord::new(notes).rotate_by(inversion.value_for(3) as usize)
+    }
+
+    /// Applies a single transformation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::neo_riemannian::{Transformation, Triad};
+    /// use note_pen::prelude::*;
+    /// let c_major = Triad::new(Note::new(Alphabet::C, Accidental::Natural, 4), Tonality::Major).unwrap();
+    /// let a_minor = c_major.transform(Transformation::R);
+    /// assert_eq!(a_minor.root.alphabet, Alphabet::A);
+    /// assert_eq!(a_minor.tonality, Tonality::Minor);
+    /// assert_eq!(a_minor.transform(Transformation::R), c_major);
+    /// ```
+    pub fn transform(&self, transformation: Transformation) -> Self {
+        let (interval, steps) = match (transformation, self.tonality) {
+            (Transformation::P, _) => (0, 0),
+            (Transformation::L, Tonality::Minor) => (-4, -2),
+            (Transformation::L, _) => (4, 2),
+            (Transformation::R, Tonality::Minor) => (3, 2),
+            (Transformation::R, _) => (-3, -2),
+        };
+        let root = self.root + Interval::new(interval);
+        let root = root
+            .respell(self.root.alphabet.shift(steps))
+            .unwrap_or(root);
+        let tonality = match self.tonality {
+            Tonality::Minor => Tonality::Major,
+            _ => Tonality::Minor,
+        };
+        Self { root, tonality }
+    }
+
+    /// Applies a sequence of transformations from left to right.
+    pub fn apply(&self, transformations: &[Transformation]) -> Self {
+        transformations
+            .iter()
+            .fold(*self, |triad, &transformation| {
+                triad.transform(transformation)
+            })
+    }
+
+    /// Finds one of the shortest sequences of transformations that turns this triad into `target`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::neo_riemannian::{Transformation, Triad};
+    /// use note_pen::prelude::*;
+    /// let c_major = Triad::new(Note::new(Alphabet::C, Accidental::Natural, 4), Tonality::Major).unwrap();
+    /// let e_major = Triad::new(Note::new(Alphabet::E, Accidental::Natural, 4), Tonality::Major).unwrap();
+    /// let path = c_major.shortest_path(&e_major);
+    /// assert_eq!(path.len(), 2);
+    /// assert_eq!(c_major.apply(&path), e_major);
+    /// ```
+    pub fn shortest_path(&self, target: &Self) -> Vec<Transformation> {
+        let mut visited = HashSet::from([self.key()]);
+        let mut queue = VecDeque::from([(*self, vec![])]);
+        while let Some((triad, path)) = queue.pop_front() {
+            if triad == *target {
+                return path;
+            }
+            for transformation in Transformation::ALL {
+                let next = triad.transform(transformation);
+                if visited.insert(next.key()) {
+                    let mut path = path.clone();
+                    path.push(transformation);
+                    queue.push_back((next, path));
+                }
+            }
+        }
+        unreachable!("every major and minor triad is reachable with P, L and R")
+    }
+
+    /// Returns the vertices of the triad's triangle on the Tonnetz, root first.
+    pub fn tonnetz(&self) -> [TonnetzCoordinate; 3] {
+        TonnetzCoordinate::from_pitch(self.root.id().simple()).triangle(self.tonality)
+    }
+
+    /// Returns the Tonnetz triangles visited when applying `transformations` from this triad.
+    ///
+    /// Unlike [`Triad::tonnetz`], the coordinates are not wrapped,
+    /// so consecutive triangles always share an edge and the path can be plotted directly.
+    pub fn tonnetz_walk(&self, transformations: &[Transformation]) -> Vec<[TonnetzCoordinate; 3]> {
+        let mut root = TonnetzCoordinate::from_pitch(self.root.id().simple());
+        let mut tonality = self.tonality;
+        let mut walk = vec![root.triangle(tonality)];
+        for &transformation in transformations {
+            let (x, y) = match (transformation, tonality) {
+                (Transformation::P, _) => (0, 0),
+                (Transformation::L, Tonality::Minor) => (0, -1),
+                (Transformation::L, _) => (0, 1),
+                (Transformation::R, Tonality::Minor) => (1, -1),
+                (Transformation::R, _) => (-1, 1),
+            };
+            root = TonnetzCoordinate::new(root.x + x, root.y + y);
+            tonality = match tonality {
+                Tonality::Minor => Tonality::Major,
+                _ => Tonality::Minor,
+            };
+            walk.push(root.triangle(tonality));
+        }
+        walk
+    }
+
+    fn key(&self) -> (RelativePitch, bool) {
+        (self.root.id().simple(), self.tonality == Tonality::Minor)
+    }
+}
+
+impl PartialEq for Triad {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Triad {}
+
+/// A point on the Tonnetz.
+///
+/// The x-axis moves by perfect fifths and the y-axis moves by major thirds,
+/// so the pitch at a point is `7x + 4y` half-steps above A.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonnetzCoordinate {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl TonnetzCoordinate {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the point for a pitch with `0 <= x < 4` and `0 <= y < 3`.
+    pub fn from_pitch(pitch: RelativePitch) -> Self {
+        (0..3)
+            .flat_map(|y| (0..4).map(move |x| Self::new(x, y)))
+            .find(|coordinate| coordinate.pitch() == RelativePitch::new(pitch.0))
+            .expect("every pitch class appears once in a 4x3 region of the Tonnetz")
+    }
+
+    pub fn pitch(&self) -> RelativePitch {
+        RelativePitch((7 * self.x + 4 * self.y).rem_euclid(12) as u8)
+    }
+
+    /// Converts the point to cartesian coordinates on a triangular grid with unit edges.
+    pub fn cartesian(&self) -> (f64, f64) {
+        let x = self.x as f64 + self.y as f64 / 2.0;
+        let y = self.y as f64 * 3f64.sqrt() / 2.0;
+        (x, y)
+    }
+
+    fn triangle(self, tonality: Tonality) -> [Self; 3] {
+        let fifth = Self::new(self.x + 1, self.y);
+        let third = match tonality {
+            Tonality::Minor => Self::new(self.x + 1, self.y - 1),
+            _ => Self::new(self.x, self.y + 1),
+        };
+        [self, third, fifth]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Alphabet};
+
+    fn triad(alphabet: Alphabet, accidental: Accidental, tonality: Tonality) -> Triad {
+        Triad::new(Note::new(alphabet, accidental, 4), tonality).unwrap()
+    }
+
+    #[test]
+    fn test_basic_transformations() {
+        let c_major = triad(Alphabet::C, Accidental::Natural, Tonality::Major);
+        assert_eq!(
+            c_major.transform(Transformation::P),
+            triad(Alphabet::C, Accidental::Natural, Tonality::Minor)
+        );
+        assert_eq!(
+            c_major.transform(Transformation::L),
+            triad(Alphabet::E, Accidental::Natural, Tonality::Minor)
+        );
+        assert_eq!(
+            c_major.transform(Transformation::R),
+            triad(Alphabet::A, Accidental::Natural, Tonality::Minor)
+        );
+        for transformation in Transformation::ALL {
+            assert_eq!(
+                c_major.transform(transformation).transform(transformation),
+                c_major
+            );
+        }
+    }
+
+    #[test]
+    fn test_spelling() {
+        let c_minor = triad(Alphabet::C, Accidental::Natural, Tonality::Minor);
+        let a_flat_major = c_minor.transform(Transformation::L);
+        assert_eq!(a_flat_major.root.alphabet, Alphabet::A);
+        assert_eq!(a_flat_major.root.accidental, Accidental::Flat);
+        let chord = a_flat_major.chord(Inversion::ROOT);
+        assert_eq!(chord.notes[1].alphabet, Alphabet::C);
+        assert_eq!(chord.notes[2].alphabet, Alphabet::E);
+        assert_eq!(chord.notes[2].accidental, Accidental::Flat);
+    }
+
+    #[test]
+    fn test_compound() {
+        let c_major = triad(Alphabet::C, Accidental::Natural, Tonality::Major);
+        let sequence = Transformation::parse_sequence("PLR").unwrap();
+        // C major -> C minor -> Ab major -> F minor
+        assert_eq!(
+            c_major.apply(&sequence),
+            triad(Alphabet::F, Accidental::Natural, Tonality::Minor)
+        );
+    }
+
+    #[test]
+    fn test_from_chord() {
+        let chord = Chord::triad_from_root(
+            Tonality::Minor,
+            Note::new(Alphabet::D, Accidental::Natural, 4),
+            Inversion::FIRST,
+        );
+        assert_eq!(
+            Triad::from_chord(&chord),
+            Some(triad(Alphabet::D, Accidental::Natural, Tonality::Minor))
+        );
+        let diminished = Chord::triad_from_root(
+            Tonality::Diminished,
+            Note::new(Alphabet::B, Accidental::Natural, 4),
+            Inversion::ROOT,
+        );
+        assert_eq!(Triad::from_chord(&diminished), None);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let c_major = triad(Alphabet::C, Accidental::Natural, Tonality::Major);
+        assert!(c_major.shortest_path(&c_major).is_empty());
+        for tonality in [Tonality::Major, Tonality::Minor] {
+            for i in 0..12 {
+                let target = Triad::new(c_major.root.increment_by(i), tonality).unwrap();
+                let path = c_major.shortest_path(&target);
+                assert!(path.len() <= 5);
+                assert_eq!(c_major.apply(&path), target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tonnetz() {
+        let c_major = triad(Alphabet::C, Accidental::Natural, Tonality::Major);
+        for (vertex, note) in c_major
+            .tonnetz()
+            .iter()
+            .zip(c_major.chord(Inversion::ROOT).notes)
+        {
+            assert_eq!(vertex.pitch(), note.id().simple());
+        }
+        let sequence = Transformation::parse_sequence("RLPR").unwrap();
+        let walk = c_major.tonnetz_walk(&sequence);
+        assert_eq!(walk.len(), 5);
+        for (triangles, i) in walk.windows(2).zip(0..) {
+            let shared = triangles[0]
+                .iter()
+                .filter(|vertex| triangles[1].contains(vertex))
+                .count();
+            assert_eq!(shared, 2);
+            let triad = c_major.apply(&sequence[..=i]);
+            assert_eq!(triangles[1][0].pitch(), triad.root.id().simple());
+        }
+    }
+}
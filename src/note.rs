@@ -1,10 +1,11 @@
 use crate::chord::Chord;
 use crate::pitch::Pitch;
 use crate::{Accidental, Alphabet, Interval};
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug, Hash)]
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Note {
     pub alphabet: Alphabet,
@@ -43,21 +44,14 @@ impl Note {
             Alphabet::F => 8,
             Alphabet::G => 10,
         };
-        let accidental = match self.accidental {
-            Accidental::None => 0,
-            Accidental::DoubleFlat => -2,
-            Accidental::Flat => -1,
-            Accidental::Natural => 0,
-            Accidental::Sharp => 1,
-            Accidental::DoubleSharp => 2,
-        };
+        let accidental = self.accidental.offset() as i64;
         let octave = (self.octave as i64 - 4) * 12;
         Pitch((offset + accidental + octave) as i16)
     }
 
     pub const fn from_id(id: Pitch) -> Self {
-        let octave = id.0 / 12 + 4;
-        let id = id.0 % 12;
+        let octave = id.0.div_euclid(12) + 4;
+        let id = id.0.rem_euclid(12);
         let (alphabet, accidental) = match id {
             0 => (Alphabet::A, Accidental::Natural),
             1 => (Alphabet::A, Accidental::Sharp),
@@ -101,6 +95,52 @@ impl Note {
     pub const fn decrement_by(&self, steps: i64) -> Self {
         Self::from_id(Pitch(self.id().0 - steps as i16))
     }
+
+    /// Raises or lowers the note by the given number of half-steps while keeping its letter,
+    /// returning `None` if that would need more than a double accidental.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let b_flat = Note::new(Alphabet::B, Accidental::Flat, 4);
+    /// assert_eq!(b_flat.altered(1).unwrap().accidental, Accidental::Natural);
+    /// assert_eq!(b_flat.altered(-1).unwrap().accidental, Accidental::DoubleFlat);
+    /// assert!(b_flat.altered(-2).is_none());
+    /// ```
+    pub const fn altered(&self, half_steps: i8) -> Option<Self> {
+        match Accidental::from_offset(self.accidental.offset() + half_steps) {
+            Some(accidental) => Some(Self::new(self.alphabet, accidental, self.octave)),
+            None => None,
+        }
+    }
+
+    /// Spells the same pitch using the given letter, if it can be reached with at most a double accidental.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let g_sharp = Note::new(Alphabet::G, Accidental::Sharp, 4);
+    /// let a_flat = g_sharp.respell(Alphabet::A).unwrap();
+    /// assert_eq!(a_flat.alphabet, Alphabet::A);
+    /// assert_eq!(a_flat.accidental, Accidental::Flat);
+    /// assert_eq!(a_flat, g_sharp);
+    /// assert!(g_sharp.respell(Alphabet::D).is_none());
+    /// ```
+    pub fn respell(&self, alphabet: Alphabet) -> Option<Self> {
+        let id = self.id().0;
+        let octave = id.div_euclid(12) + 4;
+        (octave - 1..=octave + 1)
+            .filter(|octave| (0..=u8::MAX as i16).contains(octave))
+            .find_map(|octave| {
+                let natural = Self::new(alphabet, Accidental::Natural, octave as u8);
+                let offset = id - natural.id().0;
+                if offset.abs() > 2 {
+                    return None;
+                }
+                Accidental::from_offset(offset as i8)
+                    .map(|accidental| Self::new(alphabet, accidental, octave as u8))
+            })
+    }
 }
 
 impl FromStr for Note {
@@ -114,7 +154,11 @@ impl FromStr for Note {
             Some('b') => Accidental::Flat,
             _ => Accidental::None,
         };
-        let octave = s.chars().last().map(|s| s.to_digit(10).ok_or(())).unwrap_or(Ok(0))? as u8;
+        let octave = s
+            .chars()
+            .last()
+            .map(|s| s.to_digit(10).ok_or(()))
+            .unwrap_or(Ok(0))? as u8;
         Ok(Self::new(alphabet, accidental, octave))
     }
 }
@@ -127,6 +171,12 @@ impl PartialEq for Note {
 
 impl Eq for Note {}
 
+impl Hash for Note {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
+}
+
 impl Add for Note {
     type Output = Chord;
 
@@ -138,7 +188,7 @@ impl Add for Note {
 impl Add<Interval> for Note {
     type Output = Note;
     fn add(self, interval: Interval) -> Note {
-        Note::from_id(Pitch(self.id().0 + interval.0 as i16))
+        Note::from_id(Pitch(self.id().0 + interval.0))
     }
 }
 
@@ -152,7 +202,7 @@ impl Sub for Note {
 impl Sub<Interval> for Note {
     type Output = Note;
     fn sub(self, interval: Interval) -> Note {
-        Note::from_id(Pitch(self.id().0 - interval.0 as i16))
+        Note::from_id(Pitch(self.id().0 - interval.0))
     }
 }
 
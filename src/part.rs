@@ -1,4 +1,21 @@
-use crate::measure::Measure;
+use crate::duration::Fraction;
+use crate::measure::{Measure, MeasureError, Voice};
+use crate::Clef;
+
+/// One of the staves a part is written on.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Staff {
+    /// The clef the staff starts with.
+    pub clef: Clef,
+}
+
+impl Staff {
+    #[inline]
+    pub const fn new(clef: Clef) -> Self {
+        Self { clef }
+    }
+}
 
 /// Represents an entire musical part.
 #[derive(Clone, Default)]
@@ -7,4 +24,123 @@ pub struct Part {
     pub name: Option<String>,
     pub instrument: Option<String>,
     pub measures: Vec<Measure>,
+    /// The staves of the part, numbered from 1 in order, like the two staves of a piano.
+    /// When empty, the part has a single staff with the clef of each measure.
+    pub staves: Vec<Staff>,
+}
+
+impl Part {
+    /// Creates an empty part on a treble and a bass staff, as for piano.
+    pub fn grand_staff() -> Self {
+        Self {
+            staves: vec![Staff::new(Clef::Treble), Staff::new(Clef::Bass)],
+            ..Default::default()
+        }
+    }
+
+    /// The number of staves the part is written on.
+    #[inline]
+    pub fn staff_count(&self) -> usize {
+        self.staves.len().max(1)
+    }
+
+    /// Places the items of every measure in their voices with [`Measure::voices`],
+    /// with onsets from the start of the part.
+    ///
+    /// Each measure starts where the previous one ends, by [`Measure::length`].
+    pub fn voices(&self) -> Vec<Voice<'_>> {
+        let mut voices: Vec<Voice> = vec![];
+        let mut start = Fraction::ZERO;
+        for (index, measure) in self.measures.iter().enumerate() {
+            for voice in measure.voices() {
+                let items = voice.items.into_iter().map(|mut item| {
+                    item.measure = index;
+                    item.onset = start + item.onset;
+                    item
+                });
+                match voices.iter_mut().find(|other| other.number == voice.number) {
+                    Some(other) => other.items.extend(items),
+                    None => voices.push(Voice {
+                        number: voice.number,
+                        items: items.collect(),
+                    }),
+                }
+            }
+            start = start + measure.length();
+        }
+        voices.sort_by_key(|voice| voice.number);
+        voices
+    }
+
+    /// Checks every measure with [`Measure::validate`], returning the errors with the index of their measure.
+    pub fn validate(&self) -> Result<(), Vec<(usize, MeasureError)>> {
+        let errors = self
+            .measures
+            .iter()
+            .enumerate()
+            .filter_map(|(index, measure)| measure.validate().err().map(|errors| (index, errors)))
+            .flat_map(|(index, errors)| errors.into_iter().map(move |error| (index, error)))
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_voices() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let note = |alphabet, octave| Note::new(alphabet, Accidental::Natural, octave);
+        let mut part = Part::grand_staff();
+        let mut pickup = Measure::pickup(Clef::Treble, TimeSignature::new(3, 4), key.clone());
+        pickup.push(TimedMeasureItem::note(
+            note(Alphabet::G, 4),
+            Duration::QUARTER,
+        ));
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::new(3, 4), key);
+        measure.push(TimedMeasureItem::note(note(Alphabet::C, 5), Duration::HALF));
+        measure.push(TimedMeasureItem::note(
+            note(Alphabet::B, 5),
+            Duration::QUARTER,
+        ));
+        measure.push(TimedMeasureItem::backward(Duration::new(
+            PrimitiveDuration::HALF,
+            1,
+        )));
+        let bass = |alphabet| TimedMeasureItem::note(note(alphabet, 3), Duration::QUARTER);
+        measure.push(TimedMeasureItem::forward(Duration::QUARTER).with_voice(5));
+        measure.push(bass(Alphabet::E).with_voice(5).with_staff(2));
+        measure.push(bass(Alphabet::G).with_voice(5).with_staff(2));
+        part.measures = vec![pickup, measure];
+        assert_eq!(part.staff_count(), 2);
+        let voices = part.voices();
+        assert_eq!(voices.len(), 2);
+        let onsets = |voice: &Voice| {
+            voice
+                .items
+                .iter()
+                .map(|item| item.onset)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            onsets(&voices[0]),
+            [Fraction::ZERO, Fraction::new(1, 4), Fraction::new(3, 4)]
+        );
+        assert_eq!(voices[1].number, 5);
+        assert_eq!(
+            onsets(&voices[1]),
+            [Fraction::new(1, 2), Fraction::new(3, 4)]
+        );
+        assert!(voices[1]
+            .items
+            .iter()
+            .all(|item| item.staff == 2 && item.measure == 1));
+    }
 }
@@ -1,7 +1,7 @@
 use crate::Interval;
 use derive_more::with_trait::{Add, Neg, Sub};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Add, Sub)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Add, Sub)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelativePitch(pub u8);
 
@@ -17,7 +17,7 @@ pub trait RelativeSystem {
     fn interval(first: Self, second: Self) -> Interval;
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Add, Neg)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Add, Neg)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pitch(pub i16);
 
@@ -43,7 +43,7 @@ impl Pitch {
     }
 
     pub const fn simple(&self) -> RelativePitch {
-        RelativePitch((self.0 % 12) as u8)
+        RelativePitch(self.0.rem_euclid(12) as u8)
     }
 }
 
@@ -0,0 +1,322 @@
+//! Timed events for playback, and transforms that change how they are performed
+//! without changing the notated score.
+
+use crate::duration::{Fraction, PrimitiveDuration};
+use crate::measure::{Measure, TimedMeasureItemInner};
+use crate::note::Note;
+
+/// The velocity of events before any transform, as in MIDI.
+pub const DEFAULT_VELOCITY: u8 = 80;
+
+/// Notes sounding together for playback.
+///
+/// Onsets and lengths are in whole notes from the start of the score,
+/// as floating point numbers since a performance may move them off the notated grid.
+/// Convert them to seconds with [`crate::time::TempoMap::seconds_at_offset`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaybackEvent {
+    pub onset: f64,
+    pub length: f64,
+    pub notes: Vec<Note>,
+    pub velocity: u8,
+    /// The start of the measure the event is in, which transforms count their subdivisions from.
+    pub measure_start: f64,
+    /// The length of the measure the event is in.
+    pub measure_length: f64,
+}
+
+impl PlaybackEvent {
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.onset + self.length
+    }
+
+    /// The offset of a position from the start of the event's measure.
+    ///
+    /// The end of the measure is the start of the next one, so it is counted from there.
+    fn measure_offset(&self, position: f64) -> (f64, f64) {
+        let offset = position - self.measure_start;
+        if offset >= self.measure_length {
+            (offset - self.measure_length, f64::INFINITY)
+        } else {
+            (offset, self.measure_length)
+        }
+    }
+}
+
+/// Creates the playback events of consecutive measures, one for each note or chord,
+/// in the order of their onsets.
+///
+/// Each measure starts where the previous one ends, by [`Measure::length`].
+pub fn events(measures: &[Measure]) -> Vec<PlaybackEvent> {
+    let mut events = vec![];
+    let mut measure_start = Fraction::ZERO;
+    for measure in measures {
+        for voice in measure.voices() {
+            for item in voice.items {
+                let notes = match item.item.item() {
+                    TimedMeasureItemInner::Note(note) => vec![*note],
+                    TimedMeasureItemInner::Chord(chord) => chord.notes.clone(),
+                    _ => continue,
+                };
+                events.push(PlaybackEvent {
+                    onset: (measure_start + item.onset).to_f64(),
+                    length: item.item.duration().length().to_f64(),
+                    notes,
+                    velocity: DEFAULT_VELOCITY,
+                    measure_start: measure_start.to_f64(),
+                    measure_length: measure.length().to_f64(),
+                });
+            }
+        }
+        measure_start = measure_start + measure.length();
+    }
+    events.sort_by(|a, b| a.onset.total_cmp(&b.onset));
+    events
+}
+
+/// Swing, which lengthens the first of each pair of subdivisions and shortens the second.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Swing {
+    /// The subdivision that is swung, usually eighth or sixteenth notes.
+    pub subdivision: PrimitiveDuration,
+    /// How much longer the first of each pair is than the second.
+    pub ratio: f64,
+}
+
+impl Swing {
+    /// Even subdivisions.
+    pub const STRAIGHT: f64 = 1.0;
+    /// The first of each pair is twice as long, like a triplet quarter and eighth.
+    pub const TRIPLET: f64 = 2.0;
+    /// The first of each pair is three times as long, like a dotted eighth and sixteenth.
+    pub const HARD: f64 = 3.0;
+
+    #[inline]
+    pub const fn new(subdivision: PrimitiveDuration, ratio: f64) -> Self {
+        Self { subdivision, ratio }
+    }
+
+    /// How far a position `offset` into a measure of `length` is moved when it is played.
+    ///
+    /// Pairs are counted from the start of the measure,
+    /// and a pair cut short by the end of the measure, like the last eighth of 7/8, is not swung.
+    fn shift(&self, offset: f64, length: f64) -> f64 {
+        let pair = self.subdivision.length().to_f64() * 2.0;
+        let start = (offset / pair).floor() * pair;
+        if start + pair > length {
+            return 0.0;
+        }
+        let offset = offset - start;
+        let first = pair * self.ratio / (self.ratio + 1.0);
+        let half = pair / 2.0;
+        let played = if offset < half {
+            offset / half * first
+        } else {
+            first + (offset - half) / half * (pair - first)
+        };
+        played - offset
+    }
+
+    /// Swings the events, moving both their onsets and their ends.
+    /// Pairs of subdivisions are counted from the start of each event's measure.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::playback::{PlaybackEvent, Swing, DEFAULT_VELOCITY};
+    /// let offbeat = PlaybackEvent {
+    ///     onset: 0.125,
+    ///     length: 0.125,
+    ///     notes: vec![Note::new(Alphabet::C, Accidental::Natural, 4)],
+    ///     velocity: DEFAULT_VELOCITY,
+    ///     measure_start: 0.0,
+    ///     measure_length: 1.0,
+    /// };
+    /// let mut events = [offbeat];
+    /// Swing::new(PrimitiveDuration::EIGHTH, Swing::TRIPLET).apply(&mut events);
+    /// assert!((events[0].onset - 1.0 / 6.0).abs() < 1e-9);
+    /// assert!((events[0].length - 1.0 / 12.0).abs() < 1e-9);
+    /// ```
+    pub fn apply(&self, events: &mut [PlaybackEvent]) {
+        for event in events {
+            let (offset, length) = event.measure_offset(event.onset);
+            let onset = event.onset + self.shift(offset, length);
+            let (offset, length) = event.measure_offset(event.end());
+            let end = event.end() + self.shift(offset, length);
+            event.onset = onset;
+            event.length = end - onset;
+        }
+    }
+}
+
+/// A repeating pattern of timing and velocity changes for each subdivision, like a drum groove.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrooveTemplate {
+    pub subdivision: PrimitiveDuration,
+    /// How late each subdivision is played, as a fraction of the subdivision.
+    /// Negative values are played early.
+    pub timing: Vec<f64>,
+    /// How much louder each subdivision is played.
+    pub velocity: Vec<i8>,
+}
+
+impl GrooveTemplate {
+    /// The subdivision a position `offset` into a measure falls on, if any.
+    fn step(&self, offset: f64) -> Option<usize> {
+        let steps = offset / self.subdivision.length().to_f64();
+        if (steps - steps.round()).abs() > 1e-9 {
+            return None;
+        }
+        Some(steps.round() as usize)
+    }
+
+    /// How late a position `offset` into a measure is played.
+    fn shift(&self, offset: f64) -> f64 {
+        match self.step(offset) {
+            Some(step) if !self.timing.is_empty() => {
+                self.timing[step % self.timing.len()] * self.subdivision.length().to_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Applies the template to the events that start on a subdivision,
+    /// starting the pattern again at each measure. Other events are left alone.
+    ///
+    /// The end of an event moves with the subdivision it falls on,
+    /// so it still meets the event that follows it.
+    pub fn apply(&self, events: &mut [PlaybackEvent]) {
+        for event in events {
+            let (offset, _) = event.measure_offset(event.onset);
+            let Some(step) = self.step(offset) else {
+                continue;
+            };
+            let onset = event.onset + self.shift(offset);
+            let (end_offset, _) = event.measure_offset(event.end());
+            let end = event.end() + self.shift(end_offset);
+            event.onset = onset;
+            event.length = end - onset;
+            if !self.velocity.is_empty() {
+                let velocity =
+                    event.velocity as i16 + self.velocity[step % self.velocity.len()] as i16;
+                event.velocity = velocity.clamp(1, 127) as u8;
+            }
+        }
+    }
+}
+
+/// How a performance departs from the notated rhythm.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Feel {
+    #[default]
+    Straight,
+    Swing(Swing),
+    Groove(GrooveTemplate),
+}
+
+impl Feel {
+    pub fn apply(&self, events: &mut [PlaybackEvent]) {
+        match self {
+            Self::Straight => {}
+            Self::Swing(swing) => swing.apply(events),
+            Self::Groove(groove) => groove.apply(events),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_groove() {
+        let event = |onset: f64| PlaybackEvent {
+            onset,
+            length: 0.0625,
+            notes: vec![Note::new(Alphabet::C, Accidental::Natural, 4)],
+            velocity: DEFAULT_VELOCITY,
+            measure_start: 0.0,
+            measure_length: 1.0,
+        };
+        let mut events = [event(0.0), event(0.0625), event(0.125), event(0.1)];
+        let groove = GrooveTemplate {
+            subdivision: PrimitiveDuration::SIXTEENTH,
+            timing: vec![0.0, 0.5],
+            velocity: vec![10, -10],
+        };
+        groove.apply(&mut events);
+        assert_eq!(events[0].velocity, DEFAULT_VELOCITY + 10);
+        assert_eq!(events[1].onset, 0.09375);
+        // The end stays on the next sixteenth, which is not delayed.
+        assert_eq!(events[1].end(), 0.125);
+        assert_eq!(events[1].velocity, DEFAULT_VELOCITY - 10);
+        assert_eq!(events[2].onset, 0.125);
+        assert_eq!(events[3], event(0.1));
+        // Swing leaves the notes on the beat alone.
+        let mut events = [event(0.25)];
+        Swing::new(PrimitiveDuration::EIGHTH, Swing::HARD).apply(&mut events);
+        assert_eq!(events[0].onset, 0.25);
+        assert_eq!(events[0].length, 0.09375);
+    }
+
+    #[test]
+    fn test_events() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let c = Note::new(Alphabet::C, Accidental::Natural, 4);
+        let e = Note::new(Alphabet::E, Accidental::Natural, 4);
+        let mut pickup = Measure::pickup(Clef::Treble, TimeSignature::new(2, 4), key.clone());
+        pickup.push(TimedMeasureItem::note(e, Duration::EIGHTH));
+        let mut measure = Measure::new(Clef::Treble, TimeSignature::new(2, 4), key);
+        measure.push(TimedMeasureItem::note(c, Duration::HALF));
+        measure.push(TimedMeasureItem::backward(Duration::HALF));
+        for _ in 0..4 {
+            measure.push(TimedMeasureItem::note(e, Duration::EIGHTH).with_voice(2));
+        }
+        let events = events(&[pickup, measure]);
+        let onsets = events.iter().map(|event| event.onset).collect::<Vec<_>>();
+        assert_eq!(onsets, [0.0, 0.125, 0.125, 0.25, 0.375, 0.5]);
+        assert_eq!(events[1].notes, [c]);
+        assert_eq!(events[1].length, 0.5);
+        assert_eq!(events[1].measure_start, 0.125);
+        // Swing counts from the downbeat after the pickup, not from the start of the score.
+        let mut swung = events.clone();
+        Swing::new(PrimitiveDuration::EIGHTH, Swing::TRIPLET).apply(&mut swung);
+        let expected = [
+            0.0,
+            0.125,
+            0.125,
+            0.125 + 1.0 / 6.0,
+            0.375,
+            0.375 + 1.0 / 6.0,
+        ];
+        for (event, expected) in swung.iter().zip(expected) {
+            assert!((event.onset - expected).abs() < 1e-9);
+        }
+        // Each swung eighth still ends where the next one starts.
+        assert!((swung[2].end() - swung[3].onset).abs() < 1e-9);
+        assert!((swung[5].end() - 0.625).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_swing_uneven_measure() {
+        // The last eighth of 7/8 has no partner, so it is played straight.
+        let event = |onset: f64| PlaybackEvent {
+            onset,
+            length: 0.125,
+            notes: vec![],
+            velocity: DEFAULT_VELOCITY,
+            measure_start: 0.0,
+            measure_length: 0.875,
+        };
+        let mut events = [event(0.625), event(0.75)];
+        Swing::new(PrimitiveDuration::EIGHTH, Swing::TRIPLET).apply(&mut events);
+        assert!((events[0].onset - (0.5 + 1.0 / 6.0)).abs() < 1e-9);
+        assert_eq!(events[1].onset, 0.75);
+        assert_eq!(events[1].end(), 0.875);
+    }
+}
@@ -0,0 +1,367 @@
+//! Generation of functional chord progressions.
+
+use crate::chord::Inversion;
+use crate::key::Key;
+use crate::roman_numeral::RomanNumeral;
+use crate::{Accidental, ScaleDegree, Tonality};
+use rand::Rng;
+
+// Defined with the analysis that detects them, and kept here where they were first introduced.
+pub use crate::analysis::{Cadence, HarmonicFunction};
+
+/// Generates random chord progressions that follow tonic–predominant–dominant grammar.
+///
+/// Progressions start on the tonic, only move from predominant to dominant and from dominant to tonic,
+/// and finish with the requested [`Cadence`].
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::progression::{Cadence, ProgressionGenerator};
+/// use rand::SeedableRng;
+///
+/// let key = Key::new_major(Note::new(Alphabet::G, Accidental::Natural, 4)).unwrap();
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+/// let generator = ProgressionGenerator {
+///     cadence: Cadence::Half,
+///     ..ProgressionGenerator::new(8)
+/// };
+/// let progression = generator.generate(&key, &mut rng).unwrap();
+/// assert_eq!(progression.len(), 8);
+/// assert_eq!(progression.first().unwrap().degree, 1);
+/// assert_eq!(progression.last().unwrap().degree, 5);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressionGenerator {
+    /// The number of chords to generate, including the cadence.
+    pub length: usize,
+    pub cadence: Cadence,
+    /// Allow first inversion chords, and put vii° in first inversion.
+    pub inversions: bool,
+    /// Allow seventh chords on ii, V and vii.
+    pub sevenths: bool,
+    /// Allow applied dominants, like V/V, before the chord they tonicize.
+    pub secondary_dominants: bool,
+}
+
+impl Default for ProgressionGenerator {
+    fn default() -> Self {
+        Self {
+            length: 4,
+            cadence: Cadence::PerfectAuthentic,
+            inversions: false,
+            sevenths: false,
+            secondary_dominants: false,
+        }
+    }
+}
+
+impl ProgressionGenerator {
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            ..Default::default()
+        }
+    }
+
+    /// Generates a progression of exactly [`ProgressionGenerator::length`] chords in the given key.
+    ///
+    /// Returns `None` if the length is too short to hold the cadence.
+    pub fn generate<R: Rng + ?Sized>(&self, key: &Key, rng: &mut R) -> Option<Vec<RomanNumeral>> {
+        let minor = key.tonality() == Some(Tonality::Minor);
+        let mut cadence = self.cadence_degrees();
+        let body_length = self.length.checked_sub(cadence.len())?;
+        let mut degrees: Vec<u8> = vec![];
+        for i in 0..body_length {
+            let last = i + 1 == body_length;
+            let degree = match degrees.last() {
+                None => 1,
+                Some(&previous) => {
+                    let options = Self::next_degrees(previous)
+                        .iter()
+                        .copied()
+                        .filter(|&degree| !last || Self::precedes(degree, cadence[0]))
+                        .collect::<Vec<_>>();
+                    // Falling back to the tonic keeps every phrase well-formed.
+                    if options.is_empty() {
+                        1
+                    } else {
+                        options[rng.random_range(0..options.len())]
+                    }
+                }
+            };
+            degrees.push(degree);
+        }
+        degrees.append(&mut cadence);
+
+        let cadence_start = body_length;
+        let mut progression = degrees
+            .iter()
+            .enumerate()
+            .map(|(i, &degree)| {
+                let in_cadence = i >= cadence_start;
+                self.realize(degree, minor, in_cadence, rng)
+            })
+            .collect::<Vec<_>>();
+        if self.cadence == Cadence::ImperfectAuthentic {
+            let dominant = progression.len() - 2;
+            progression[dominant].inversion = Inversion::FIRST;
+        }
+        if self.cadence == Cadence::PhrygianHalf {
+            let predominant = progression.len() - 2;
+            progression[predominant] = RomanNumeral::triad(4, Tonality::Minor, Inversion::FIRST);
+        }
+
+        if self.secondary_dominants {
+            for i in 1..cadence_start {
+                let target = degrees[i + 1];
+                let applicable = HarmonicFunction::of_degree(degrees[i])
+                    != Some(HarmonicFunction::Dominant)
+                    && target != 1
+                    && progression[i + 1].quality != Tonality::Diminished;
+                if applicable && rng.random_bool(0.25) {
+                    progression[i] = self.secondary_dominant(&progression[i + 1], rng);
+                }
+            }
+        }
+        Some(progression)
+    }
+
+    fn cadence_degrees(&self) -> Vec<u8> {
+        match self.cadence {
+            Cadence::PerfectAuthentic | Cadence::ImperfectAuthentic => vec![5, 1],
+            Cadence::Half => vec![5],
+            Cadence::PhrygianHalf => vec![4, 5],
+            Cadence::Plagal => vec![4, 1],
+            Cadence::Deceptive => vec![5, 6],
+        }
+    }
+
+    fn next_degrees(degree: u8) -> &'static [u8] {
+        match degree {
+            1 => &[2, 4, 5, 6, 7],
+            6 => &[2, 4],
+            4 => &[1, 2, 5, 7],
+            2 => &[5, 7],
+            5 => &[1, 6],
+            7 => &[1],
+            _ => &[1],
+        }
+    }
+
+    /// Whether `degree` may come directly before the first chord of the cadence.
+    fn precedes(degree: u8, next: u8) -> bool {
+        degree != next && Self::next_degrees(degree).contains(&next)
+    }
+
+    fn realize<R: Rng + ?Sized>(
+        &self,
+        degree: u8,
+        minor: bool,
+        in_cadence: bool,
+        rng: &mut R,
+    ) -> RomanNumeral {
+        let mut numeral = match (minor, degree) {
+            (true, 5) => RomanNumeral::triad(5, Tonality::Major, Inversion::ROOT),
+            (true, 7) => RomanNumeral::triad(7, Tonality::Diminished, Inversion::ROOT),
+            (true, _) => RomanNumeral::minor_chord(degree, Inversion::ROOT),
+            (false, _) => RomanNumeral::major_chord(degree, Inversion::ROOT),
+        };
+        let seventh = self.sevenths
+            && match degree {
+                5 => rng.random_bool(0.5),
+                2 | 7 => rng.random_bool(0.3),
+                _ => false,
+            };
+        if seventh {
+            // vii in major can be either half or fully diminished, and ii in minor is half diminished.
+            let half_diminished = match (minor, degree) {
+                (false, 7) => rng.random_bool(0.5),
+                (true, 2) => true,
+                _ => false,
+            };
+            if half_diminished {
+                numeral.quality = Tonality::HalfDiminished;
+            }
+            numeral
+                .additional_notes
+                .push(ScaleDegree::new(7, Accidental::None));
+        }
+        if self.inversions && !in_cadence {
+            numeral.inversion = if degree == 7 || rng.random_bool(0.3) {
+                Inversion::FIRST
+            } else {
+                Inversion::ROOT
+            };
+        }
+        numeral
+    }
+
+    fn secondary_dominant<R: Rng + ?Sized>(
+        &self,
+        target: &RomanNumeral,
+        rng: &mut R,
+    ) -> RomanNumeral {
+        let target = RomanNumeral::triad(target.degree, target.quality, Inversion::ROOT);
+        let mut numeral = if self.sevenths && rng.random_bool(0.5) {
+            RomanNumeral::seventh_chord(5, Tonality::Major, Inversion::ROOT)
+        } else {
+            RomanNumeral::triad(5, Tonality::Major, Inversion::ROOT)
+        };
+        if self.inversions && rng.random_bool(0.3) {
+            numeral.inversion = Inversion::FIRST;
+        }
+        numeral.with_secondary(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::Note;
+    use crate::Alphabet;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn is_valid(progression: &[RomanNumeral]) -> bool {
+        progression.windows(2).all(|pair| {
+            let from = HarmonicFunction::of_degree(pair[0].degree);
+            let to = HarmonicFunction::of_degree(pair[1].degree);
+            !matches!(
+                (from, to),
+                (
+                    Some(HarmonicFunction::Dominant),
+                    Some(HarmonicFunction::Predominant)
+                )
+            )
+        })
+    }
+
+    #[test]
+    fn test_cadences() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let endings = [
+            (Cadence::PerfectAuthentic, [5, 1]),
+            (Cadence::ImperfectAuthentic, [5, 1]),
+            (Cadence::PhrygianHalf, [4, 5]),
+            (Cadence::Plagal, [4, 1]),
+            (Cadence::Deceptive, [5, 6]),
+        ];
+        for (cadence, ending) in endings {
+            for length in 2..10 {
+                let generator = ProgressionGenerator {
+                    cadence,
+                    ..ProgressionGenerator::new(length)
+                };
+                let progression = generator.generate(&key, &mut rng).unwrap();
+                assert_eq!(progression.len(), length);
+                let degrees = progression.iter().map(|n| n.degree).collect::<Vec<_>>();
+                assert_eq!(degrees[length - 2..], ending);
+                assert!(is_valid(&progression));
+            }
+            // Too short for the cadence.
+            let generator = ProgressionGenerator {
+                cadence,
+                ..ProgressionGenerator::new(1)
+            };
+            assert_eq!(generator.generate(&key, &mut rng), None);
+        }
+        let half = ProgressionGenerator {
+            cadence: Cadence::Half,
+            ..ProgressionGenerator::new(1)
+        };
+        assert_eq!(half.generate(&key, &mut rng).unwrap().len(), 1);
+        assert_eq!(ProgressionGenerator::new(0).generate(&key, &mut rng), None);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        let generator = ProgressionGenerator {
+            inversions: true,
+            sevenths: true,
+            secondary_dominants: true,
+            ..ProgressionGenerator::new(12)
+        };
+        let first = generator
+            .generate(&key, &mut StdRng::seed_from_u64(42))
+            .unwrap();
+        let second = generator
+            .generate(&key, &mut StdRng::seed_from_u64(42))
+            .unwrap();
+        assert_eq!(
+            first.iter().map(|n| n.degree).collect::<Vec<_>>(),
+            second.iter().map(|n| n.degree).collect::<Vec<_>>()
+        );
+        assert_eq!(first[0].degree, 1);
+        // The dominant of a minor key is major.
+        let dominant = &first[first.len() - 2];
+        assert_eq!(dominant.quality, Tonality::Major);
+        let chord = dominant.chord(&key);
+        assert!(chord
+            .notes
+            .contains(&Note::new(Alphabet::G, Accidental::Sharp, 4)));
+    }
+
+    #[test]
+    fn test_minor_supertonic_seventh() {
+        let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let generator = ProgressionGenerator {
+            sevenths: true,
+            ..ProgressionGenerator::new(8)
+        };
+        let mut found = 0;
+        for _ in 0..100 {
+            let progression = generator.generate(&key, &mut rng).unwrap();
+            for numeral in progression {
+                if numeral.degree != 2 || numeral.additional_notes.is_empty() {
+                    continue;
+                }
+                // iiø7 stays in the key: B D F A, not B D F A♭.
+                assert_eq!(numeral.quality, Tonality::HalfDiminished);
+                let notes = numeral
+                    .chord(&key)
+                    .notes
+                    .iter()
+                    .map(|note| (note.alphabet, note.accidental))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    notes,
+                    [
+                        (Alphabet::B, Accidental::Natural),
+                        (Alphabet::D, Accidental::Natural),
+                        (Alphabet::F, Accidental::Natural),
+                        (Alphabet::A, Accidental::Natural),
+                    ]
+                );
+                found += 1;
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn test_many() {
+        let key = Key::new_major(Note::new(Alphabet::E, Accidental::Flat, 4)).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let generator = ProgressionGenerator {
+            inversions: true,
+            sevenths: true,
+            ..ProgressionGenerator::new(16)
+        };
+        for _ in 0..1000 {
+            let progression = generator.generate(&key, &mut rng).unwrap();
+            assert_eq!(progression.len(), 16);
+            assert!(is_valid(&progression));
+            for numeral in progression {
+                assert_eq!(
+                    numeral.chord(&key).notes.len(),
+                    3 + numeral.additional_notes.len()
+                );
+            }
+        }
+    }
+}
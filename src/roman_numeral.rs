@@ -1,14 +1,78 @@
 use crate::chord::{Chord, Inversion};
 use crate::key::{Key, Scale};
-use crate::{Accidental, ScaleDegree, Tonality};
+use crate::note::Note;
+use crate::{Accidental, Interval, ScaleDegree, Tonality};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Clone, Debug)]
+/// Chords that are written with a name instead of a roman numeral.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NamedChord {
+    /// The tonic triad in second inversion, acting as a dominant: Cad64.
+    CadentialSixFour,
+    /// The major triad on the lowered second degree: N6.
+    Neapolitan,
+    /// It+6
+    ItalianSixth,
+    /// Fr+6
+    FrenchSixth,
+    /// Ger+6
+    GermanSixth,
+}
+
+impl NamedChord {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::CadentialSixFour => "Cad",
+            Self::Neapolitan => "N",
+            Self::ItalianSixth => "It",
+            Self::FrenchSixth => "Fr",
+            Self::GermanSixth => "Ger",
+        }
+    }
+
+    /// The number of notes and the usual inversion of chords with a fixed position,
+    /// which are the ones written without inversion figures.
+    const fn fixed_position(&self) -> Option<(u8, Inversion)> {
+        match self {
+            Self::CadentialSixFour => Some((3, Inversion::SECOND)),
+            Self::ItalianSixth => Some((3, Inversion::ROOT)),
+            Self::FrenchSixth | Self::GermanSixth => Some((4, Inversion::ROOT)),
+            Self::Neapolitan => None,
+        }
+    }
+}
+
+/// A chord described by its position in a key, like V7 or viio6.
+///
+/// Roman numerals can be parsed from and printed as standard analysis strings.
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// let numeral: RomanNumeral = "V6/5".parse().unwrap();
+/// assert_eq!(numeral.degree, 5);
+/// assert_eq!(numeral.inversion, Inversion::FIRST);
+/// assert_eq!(numeral.to_string(), "V65");
+/// let applied: RomanNumeral = "viio7/ii".parse().unwrap();
+/// assert_eq!(applied.secondary.as_ref().unwrap().degree, 2);
+/// assert_eq!(applied.to_string(), "viio7/ii");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RomanNumeral {
     pub degree: u8,
     pub quality: Tonality,
     pub inversion: Inversion,
     pub additional_notes: Vec<ScaleDegree>,
+    /// The alteration of the root relative to the scale, like the flat in bVI.
+    pub alteration: Accidental,
+    /// The chord being tonicized by an applied chord, like the second V in V/V.
+    pub secondary: Option<Box<RomanNumeral>>,
+    /// Set for chords written by name, like N6 or Ger+6.
+    ///
+    /// The other fields still describe the chord, so a Neapolitan is a major bII.
+    pub named: Option<NamedChord>,
 }
 
 impl Default for RomanNumeral {
@@ -18,6 +82,9 @@ impl Default for RomanNumeral {
             quality: Tonality::Major,
             inversion: Inversion::ROOT,
             additional_notes: vec![],
+            alteration: Accidental::None,
+            secondary: None,
+            named: None,
         }
     }
 }
@@ -58,28 +125,252 @@ impl RomanNumeral {
         }
     }
 
+    /// Creates a chord borrowed from the parallel minor for use in a major key, like iv, bVI or bVII.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let flat_six = RomanNumeral::borrowed(6, Inversion::ROOT);
+    /// assert_eq!(flat_six.to_string(), "bVI");
+    /// let chord = flat_six.chord(&key);
+    /// assert_eq!(chord.notes[0].accidental, Accidental::Flat);
+    /// assert_eq!(chord.notes[2].alphabet, Alphabet::E);
+    /// assert_eq!(chord.notes[2].accidental, Accidental::Flat);
+    /// ```
+    pub fn borrowed(degree: u8, inversion: Inversion) -> Self {
+        let alteration = match degree {
+            3 | 6 | 7 => Accidental::Flat,
+            _ => Accidental::None,
+        };
+        Self {
+            alteration,
+            ..Self::minor_chord(degree, inversion)
+        }
+    }
+
+    /// Creates a Neapolitan chord, the major triad on the lowered second degree.
+    ///
+    /// It is usually in first inversion, as N6.
+    pub fn neapolitan(inversion: Inversion) -> Self {
+        Self {
+            degree: 2,
+            inversion,
+            alteration: Accidental::Flat,
+            named: Some(NamedChord::Neapolitan),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a cadential six-four, the tonic triad in second inversion.
+    ///
+    /// The triad is minor in minor keys.
+    pub fn cadential_six_four() -> Self {
+        Self {
+            inversion: Inversion::SECOND,
+            named: Some(NamedChord::CadentialSixFour),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an Italian, French or German augmented sixth chord,
+    /// returning `None` if `kind` is not an augmented sixth.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::roman_numeral::NamedChord;
+    /// let key = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+    /// let german = RomanNumeral::augmented_sixth(NamedChord::GermanSixth).unwrap();
+    /// let chord = german.chord(&key);
+    /// let spelling = chord.notes.iter().map(|note| (note.alphabet, note.accidental)).collect::<Vec<_>>();
+    /// assert_eq!(spelling, vec![
+    ///     (Alphabet::F, Accidental::Natural),
+    ///     (Alphabet::A, Accidental::Natural),
+    ///     (Alphabet::C, Accidental::Natural),
+    ///     (Alphabet::D, Accidental::Sharp),
+    /// ]);
+    /// ```
+    pub fn augmented_sixth(kind: NamedChord) -> Option<Self> {
+        match kind {
+            NamedChord::ItalianSixth | NamedChord::FrenchSixth | NamedChord::GermanSixth => {
+                Some(Self {
+                    degree: 6,
+                    alteration: Accidental::Flat,
+                    named: Some(kind),
+                    ..Default::default()
+                })
+            }
+            NamedChord::CadentialSixFour | NamedChord::Neapolitan => None,
+        }
+    }
+
+    /// Creates a seventh chord.
+    ///
+    /// The seventh is the scale note, except for [`Tonality::Diminished`] chords which take a diminished seventh
+    /// and [`Tonality::HalfDiminished`] chords which take a minor seventh.
     pub fn seventh_chord(degree: u8, quality: Tonality, inversion: Inversion) -> Self {
         Self {
             degree,
             quality,
             inversion,
             additional_notes: vec![ScaleDegree::new(7, Accidental::None)],
+            ..Default::default()
         }
     }
 
-    pub fn chord(&self, key: &Key) -> Chord {
+    /// Turns the chord into an applied chord that tonicizes `target`, like V/V.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let five_of_five = RomanNumeral::seventh_chord(5, Tonality::Major, Inversion::ROOT)
+    ///     .with_secondary(RomanNumeral::major_chord(5, Inversion::ROOT));
+    /// assert_eq!(five_of_five.to_string(), "V7/V");
+    /// let chord = five_of_five.chord(&key);
+    /// assert_eq!(chord.notes[0].alphabet, Alphabet::D);
+    /// assert_eq!(chord.notes[1].alphabet, Alphabet::F);
+    /// assert_eq!(chord.notes[1].accidental, Accidental::Sharp);
+    /// assert_eq!(chord.notes[3].alphabet, Alphabet::C);
+    /// ```
+    pub fn with_secondary(self, target: RomanNumeral) -> Self {
+        Self {
+            secondary: Some(Box::new(target)),
+            ..self
+        }
+    }
+
+    /// Returns the key in which this chord is the tonic.
+    ///
+    /// Major and augmented chords tonicize a major key and the rest tonicize a minor key.
+    /// Roots without a standard key signature, like D♯ major, are spelled enharmonically.
+    pub fn tonicized_key(&self, key: &Key) -> Key {
+        let root = self.root(&self.realization_key(key));
+        let build = |root: Note| match self.quality {
+            Tonality::Major | Tonality::Augmented => Key::new_major(root),
+            _ => Key::new_minor(root),
+        };
+        build(root)
+            .or_else(|| root.respell(root.alphabet.next()).and_then(build))
+            .or_else(|| root.respell(root.alphabet.prev()).and_then(build))
+            .unwrap_or_else(|| key.clone())
+    }
+
+    /// Returns the key the chord is realized in, which is tonicized for applied chords.
+    fn realization_key(&self, key: &Key) -> Key {
+        match &self.secondary {
+            Some(target) => target.tonicized_key(key),
+            None => key.clone(),
+        }
+    }
+
+    /// Returns the root of the chord in the key it is realized in.
+    fn root(&self, key: &Key) -> Note {
         let scale = Scale::from(key);
-        let id1 = self.degree as usize - 1;
-        let id2 = ((self.degree + 2) as usize - 1) % 7;
-        let id3 = ((self.degree + 4) as usize - 1) % 7;
-        let mut notes = vec![scale.notes[id1], scale.notes[id2], scale.notes[id3]];
-        for additional_note in self.additional_notes.iter() {
-            let id = (self.degree + additional_note.degree.get() - 1) % 7;
-            let mut note = scale.notes[id as usize];
-            match additional_note.quality {
-                Accidental::None => {}
-                _ => note.accidental = additional_note.quality,
+        let mut root = scale.notes[scale_index(self.degree, 0)];
+        let diminished = matches!(
+            self.quality,
+            Tonality::Diminished | Tonality::HalfDiminished
+        );
+        if self.degree == 7 && diminished && key.tonality() == Some(Tonality::Minor) {
+            root = root.altered(1).unwrap_or(root);
+        }
+        root.altered(self.alteration.offset()).unwrap_or(root)
+    }
+
+    /// Returns whether the chord has a seventh.
+    pub fn is_seventh(&self) -> bool {
+        self.additional_notes
+            .iter()
+            .any(|note| note.degree.get() == 7)
+    }
+
+    /// Realizes the roman numeral as a chord in the given key.
+    ///
+    /// The root, third and fifth are taken from the scale and then altered to match [`RomanNumeral::quality`],
+    /// so a major V in a minor key gets its raised leading tone.
+    /// A diminished vii in a minor key is built on the raised leading tone.
+    /// The root is then altered by [`RomanNumeral::alteration`].
+    /// Additional notes are counted from the root, with their quality applied as an alteration of the scale note.
+    ///
+    /// Applied chords are realized in the key of [`RomanNumeral::secondary`], see [`RomanNumeral::tonicized_key`].
+    ///
+    /// Augmented sixth chords are built from the tonic rather than a root, with the lowered sixth degree first:
+    /// ♭6, 1 and ♯4, adding 2 for the French sixth or ♭3 for the German sixth.
+    pub fn chord(&self, key: &Key) -> Chord {
+        let key = self.realization_key(key);
+        match self.named {
+            Some(NamedChord::CadentialSixFour) => {
+                let quality = match key.tonality() {
+                    Some(Tonality::Minor) => Tonality::Minor,
+                    _ => Tonality::Major,
+                };
+                let tonic = Self {
+                    quality,
+                    secondary: None,
+                    named: None,
+                    ..self.clone()
+                };
+                return tonic.chord(&key);
+            }
+            Some(
+                named @ (NamedChord::ItalianSixth
+                | NamedChord::FrenchSixth
+                | NamedChord::GermanSixth),
+            ) => {
+                let tonic = Scale::from(&key).notes[0];
+                let above_tonic = |half_steps: i16, letters: i64| {
+                    let note = tonic + Interval::new(half_steps);
+                    note.respell(tonic.alphabet.shift(letters)).unwrap_or(note)
+                };
+                let mut notes = vec![above_tonic(-4, -2), tonic];
+                match named {
+                    NamedChord::FrenchSixth => notes.push(above_tonic(2, 1)),
+                    NamedChord::GermanSixth => notes.push(above_tonic(3, 2)),
+                    _ => {}
+                }
+                notes.push(above_tonic(6, 3));
+                let size = notes.len() as u8;
+                return Chord::new(notes).rotate_by(self.inversion.value_for(size) as usize);
             }
+            _ => {}
+        }
+        let scale = Scale::from(&key);
+        let scale_note = |offset: u8| scale.notes[scale_index(self.degree, offset)];
+        let root = self.root(&key);
+        let (third, fifth) = match self.quality {
+            Tonality::Major => (Interval::MAJOR_THIRD, Interval::PERFECT_FIFTH),
+            Tonality::Minor => (Interval::MINOR_THIRD, Interval::PERFECT_FIFTH),
+            Tonality::Diminished | Tonality::HalfDiminished => {
+                (Interval::MINOR_THIRD, Interval::TRITONE)
+            }
+            Tonality::Augmented => (Interval::MAJOR_THIRD, Interval::AUGMENTED_FIFTH),
+        };
+        // Diminished chords pick their seventh by quality rather than from the scale.
+        let seventh = match self.quality {
+            Tonality::Diminished => Some(Interval::MAJOR_SIXTH),
+            Tonality::HalfDiminished => Some(Interval::MINOR_SEVENTH),
+            _ => None,
+        };
+        let mut notes = vec![
+            root,
+            fit(scale_note(2), root, third),
+            fit(scale_note(4), root, fifth),
+        ];
+        for additional_note in self.additional_notes.iter() {
+            let note = scale_note(additional_note.degree.get() - 1);
+            let note = match seventh {
+                Some(seventh)
+                    if additional_note.degree.get() == 7
+                        && additional_note.quality.offset() == 0 =>
+                {
+                    fit(note, root, seventh)
+                }
+                _ => note
+                    .altered(additional_note.quality.offset())
+                    .unwrap_or(note),
+            };
             notes.push(note);
         }
         let chord = Chord::new(notes);
@@ -90,14 +381,272 @@ impl RomanNumeral {
     }
 }
 
+/// The index in the scale of the note `offset` steps above `degree`.
+///
+/// Degrees outside 1 to 7 wrap around the scale, so 8 is the tonic and 0 the seventh degree.
+const fn scale_index(degree: u8, offset: u8) -> usize {
+    (degree as usize + offset as usize + 6) % 7
+}
+
+/// Alters `note` so that it lies `interval` above `root`, keeping its letter when possible.
+fn fit(note: Note, root: Note, interval: Interval) -> Note {
+    let target = (root + interval).id().simple().0 as i8;
+    let offset = (target - note.id().simple().0 as i8 + 6).rem_euclid(12) - 6;
+    note.altered(offset).unwrap_or(note)
+}
+
+/// Writes an analysis string that parses back to the same numeral.
+///
+/// A Neapolitan in root position is written N53, since N alone means N6.
+/// Cadential six-fours and augmented sixths in an unusual inversion are followed by
+/// the letter of the inversion, from `a` for root position to `d` for third inversion,
+/// like Ger+6b for a German sixth over the tonic.
+/// Degrees outside 1 to 7 wrap around the scale, as in [`RomanNumeral::chord`].
+impl Display for RomanNumeral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.named {
+            Some(named) => {
+                write!(f, "{}", named.name())?;
+                match named.fixed_position() {
+                    Some((size, usual)) => {
+                        let figures = match named {
+                            NamedChord::CadentialSixFour => "64",
+                            _ => "+6",
+                        };
+                        write!(f, "{}", figures)?;
+                        let inversion = self.inversion.value_for(size);
+                        if inversion != usual.value() {
+                            write!(f, "{}", (b'a' + inversion) as char)?;
+                        }
+                    }
+                    None => match figures(self.is_seventh(), self.inversion) {
+                        "" => write!(f, "53")?,
+                        figures => write!(f, "{}", figures)?,
+                    },
+                }
+            }
+            None => {
+                let accidental = match self.alteration {
+                    Accidental::DoubleFlat => "bb",
+                    Accidental::Flat => "b",
+                    Accidental::Natural => "♮",
+                    Accidental::Sharp => "#",
+                    Accidental::DoubleSharp => "##",
+                    Accidental::None => "",
+                };
+                let numeral =
+                    ["I", "II", "III", "IV", "V", "VI", "VII"][scale_index(self.degree, 0)];
+                let (numeral, symbol) = match self.quality {
+                    Tonality::Major => (numeral.to_string(), ""),
+                    Tonality::Augmented => (numeral.to_string(), "+"),
+                    Tonality::Minor => (numeral.to_lowercase(), ""),
+                    Tonality::Diminished => (numeral.to_lowercase(), "o"),
+                    Tonality::HalfDiminished => (numeral.to_lowercase(), "ø"),
+                };
+                let figures = figures(self.is_seventh(), self.inversion);
+                write!(f, "{}{}{}{}", accidental, numeral, symbol, figures)?;
+            }
+        }
+        if let Some(secondary) = &self.secondary {
+            write!(f, "/{}", secondary)?;
+        }
+        Ok(())
+    }
+}
+
+fn figures(seventh: bool, inversion: Inversion) -> &'static str {
+    match (seventh, inversion.value_for(if seventh { 4 } else { 3 })) {
+        (false, 0) => "",
+        (false, 1) => "6",
+        (false, _) => "64",
+        (true, 0) => "7",
+        (true, 1) => "65",
+        (true, 2) => "43",
+        (true, _) => "42",
+    }
+}
+
+/// Parses an analysis string such as `V6/5`, `viio7`, `ii42`, `bVI`, `V7/V`, `Cad64`, `N6` or `Ger+6`.
+///
+/// Upper case numerals are major and lower case numerals are minor,
+/// `o` (or `°`) marks a diminished chord, `ø` (or `%`) a half-diminished chord and `+` an augmented one.
+/// A leading `b`, `#` or `n` (or `♭`, `♯` or `♮`) alters the root.
+/// Figures may be written with or without slashes, and a trailing `/` introduces the tonicized chord.
+impl FromStr for RomanNumeral {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numeral, rest) = parse_numeral(s)?;
+        match rest.strip_prefix('/') {
+            Some(secondary) => Ok(Self {
+                secondary: Some(Box::new(secondary.parse()?)),
+                ..numeral
+            }),
+            None if rest.is_empty() => Ok(numeral),
+            None => Err(()),
+        }
+    }
+}
+
+/// Parses a numeral up to the slash that introduces a secondary chord, returning the rest of the string.
+fn parse_numeral(s: &str) -> Result<(RomanNumeral, &str), ()> {
+    let named = [
+        NamedChord::CadentialSixFour,
+        NamedChord::Neapolitan,
+        NamedChord::ItalianSixth,
+        NamedChord::FrenchSixth,
+        NamedChord::GermanSixth,
+    ]
+    .into_iter()
+    .find_map(|named| s.strip_prefix(named.name()).map(|rest| (named, rest)));
+    if let Some((named, rest)) = named {
+        let rest = rest.strip_prefix('+').unwrap_or(rest);
+        let (figures, rest) = split_figures(rest);
+        // The letter of an unusual inversion, written by `Display`.
+        let (inversion, rest) = match (named.fixed_position(), rest.chars().next()) {
+            (Some((size, _)), Some(letter @ 'a'..='d')) if (letter as u8 - b'a') < size => {
+                (Some(Inversion::new(letter as u8 - b'a')), &rest[1..])
+            }
+            _ => (None, rest),
+        };
+        let numeral = match (named, figures.as_str()) {
+            (NamedChord::CadentialSixFour, "" | "64") => RomanNumeral::cadential_six_four(),
+            (NamedChord::Neapolitan, _) => {
+                let (seventh, inversion) = parse_figures(&figures)?;
+                RomanNumeral {
+                    additional_notes: seventh_notes(seventh),
+                    ..RomanNumeral::neapolitan(if figures.is_empty() {
+                        Inversion::FIRST
+                    } else {
+                        inversion
+                    })
+                }
+            }
+            (NamedChord::ItalianSixth, "" | "6")
+            | (NamedChord::FrenchSixth, "" | "6" | "43")
+            | (NamedChord::GermanSixth, "" | "6" | "65") => {
+                RomanNumeral::augmented_sixth(named).ok_or(())?
+            }
+            _ => return Err(()),
+        };
+        let numeral = match inversion {
+            Some(inversion) => RomanNumeral {
+                inversion,
+                ..numeral
+            },
+            None => numeral,
+        };
+        return Ok((numeral, rest));
+    }
+
+    let (alteration, s) = [
+        ("bb", Accidental::DoubleFlat),
+        ("##", Accidental::DoubleSharp),
+        ("b", Accidental::Flat),
+        ("♭", Accidental::Flat),
+        ("n", Accidental::Natural),
+        ("♮", Accidental::Natural),
+        ("#", Accidental::Sharp),
+        ("♯", Accidental::Sharp),
+    ]
+    .into_iter()
+    .find_map(|(prefix, accidental)| s.strip_prefix(prefix).map(|s| (accidental, s)))
+    .unwrap_or((Accidental::None, s));
+
+    let length = s
+        .find(|c: char| !matches!(c, 'I' | 'V' | 'i' | 'v'))
+        .unwrap_or(s.len());
+    let (numeral, s) = s.split_at(length);
+    let degree = match numeral.to_uppercase().as_str() {
+        "I" => 1,
+        "II" => 2,
+        "III" => 3,
+        "IV" => 4,
+        "V" => 5,
+        "VI" => 6,
+        "VII" => 7,
+        _ => return Err(()),
+    };
+    let upper = numeral.chars().all(|c| c.is_uppercase());
+    if !upper && numeral.chars().any(|c| c.is_uppercase()) {
+        return Err(());
+    }
+
+    let mut chars = s.chars();
+    let (quality, s) = match (chars.next(), upper) {
+        (Some('+'), true) => (Tonality::Augmented, chars.as_str()),
+        (Some('o' | '°'), false) => (Tonality::Diminished, chars.as_str()),
+        (Some('ø' | '%'), false) => (Tonality::HalfDiminished, chars.as_str()),
+        (_, true) => (Tonality::Major, s),
+        (_, false) => (Tonality::Minor, s),
+    };
+
+    let (figures, rest) = split_figures(s);
+    let (seventh, inversion) = parse_figures(&figures)?;
+    Ok((
+        RomanNumeral {
+            degree,
+            quality,
+            inversion,
+            additional_notes: seventh_notes(seventh),
+            alteration,
+            ..Default::default()
+        },
+        rest,
+    ))
+}
+
+/// Splits the figures off the start of a string, dropping slashes between digits.
+fn split_figures(s: &str) -> (String, &str) {
+    let mut figures = String::new();
+    let mut rest = s;
+    loop {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_digit() => {
+                figures.push(c);
+                rest = chars.as_str();
+            }
+            Some('/')
+                if !figures.is_empty() && chars.next().is_some_and(|c| c.is_ascii_digit()) =>
+            {
+                rest = &rest[1..];
+            }
+            _ => return (figures, rest),
+        }
+    }
+}
+
+fn parse_figures(figures: &str) -> Result<(bool, Inversion), ()> {
+    match figures {
+        "" | "5" | "53" => Ok((false, Inversion::ROOT)),
+        "6" | "63" => Ok((false, Inversion::FIRST)),
+        "64" => Ok((false, Inversion::SECOND)),
+        "7" | "753" => Ok((true, Inversion::ROOT)),
+        "65" | "653" => Ok((true, Inversion::FIRST)),
+        "43" | "643" => Ok((true, Inversion::SECOND)),
+        "42" | "642" | "2" => Ok((true, Inversion::THIRD)),
+        _ => Err(()),
+    }
+}
+
+fn seventh_notes(seventh: bool) -> Vec<ScaleDegree> {
+    if seventh {
+        vec![ScaleDegree::new(7, Accidental::None)]
+    } else {
+        vec![]
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::NamedChord;
     use crate::chord::Inversion;
     use crate::key::Key;
     use crate::note::Note;
     use crate::prelude::RomanNumeral;
     use crate::Accidental::Natural;
-    use crate::Alphabet;
+    use crate::{Accidental, Alphabet, Tonality};
 
     #[test]
     fn test_inversion() {
@@ -108,4 +657,341 @@ mod tests {
         assert_eq!(chord.notes[1], Note::new(Alphabet::D, Natural, 4));
         assert_eq!(chord.notes[2], Note::new(Alphabet::G, Natural, 5));
     }
+
+    #[test]
+    fn test_parse() {
+        let numeral: RomanNumeral = "ii42".parse().unwrap();
+        assert_eq!(numeral.degree, 2);
+        assert_eq!(numeral.quality, Tonality::Minor);
+        assert_eq!(numeral.inversion, Inversion::THIRD);
+        assert!(numeral.is_seventh());
+
+        let numeral: RomanNumeral = "bVI".parse().unwrap();
+        assert_eq!(numeral.degree, 6);
+        assert_eq!(numeral.alteration, Accidental::Flat);
+        assert_eq!(numeral.quality, Tonality::Major);
+
+        let numeral: RomanNumeral = "V7/V".parse().unwrap();
+        assert_eq!(numeral.degree, 5);
+        assert!(numeral.is_seventh());
+        assert_eq!(
+            *numeral.secondary.unwrap(),
+            RomanNumeral::major_chord(5, Inversion::ROOT)
+        );
+
+        let numeral: RomanNumeral = "N6".parse().unwrap();
+        assert_eq!(numeral.named, Some(NamedChord::Neapolitan));
+        assert_eq!(numeral.degree, 2);
+        assert_eq!(numeral.inversion, Inversion::FIRST);
+
+        let numeral: RomanNumeral = "Cad64".parse().unwrap();
+        assert_eq!(numeral.named, Some(NamedChord::CadentialSixFour));
+        assert_eq!(numeral.inversion, Inversion::SECOND);
+
+        for invalid in ["", "X", "Vi", "V8", "V/", "Ger+7", "iio+"] {
+            assert!(invalid.parse::<RomanNumeral>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let canonical = [
+            "I", "V65", "viio7", "viiø7", "ii42", "bVI", "V7/V", "viio7/ii", "IV/IV", "III+",
+            "vi6", "V43/V/V", "Cad64", "N6", "It+6", "Fr+6", "Ger+6", "Ger+6/V", "#ivo7",
+        ];
+        for s in canonical {
+            let numeral: RomanNumeral = s.parse().unwrap();
+            assert_eq!(numeral.to_string(), s);
+            assert_eq!(numeral.to_string().parse::<RomanNumeral>(), Ok(numeral));
+        }
+        let aliases = [
+            ("V6/5", "V65"),
+            ("V4/3", "V43"),
+            ("V2", "V42"),
+            ("vii°7", "viio7"),
+            ("ii%7", "iiø7"),
+            ("Ger65", "Ger+6"),
+        ];
+        for (alias, canonical) in aliases {
+            assert_eq!(
+                alias.parse::<RomanNumeral>().unwrap().to_string(),
+                canonical
+            );
+        }
+    }
+
+    #[test]
+    fn test_constructors_round_trip() {
+        let inversions = [
+            Inversion::ROOT,
+            Inversion::FIRST,
+            Inversion::SECOND,
+            Inversion::THIRD,
+        ];
+        let qualities = [
+            Tonality::Major,
+            Tonality::Minor,
+            Tonality::Diminished,
+            Tonality::Augmented,
+            Tonality::HalfDiminished,
+        ];
+        let mut numerals = vec![RomanNumeral::default(), RomanNumeral::cadential_six_four()];
+        for degree in 1..=7 {
+            for inversion in inversions {
+                for quality in qualities {
+                    numerals.push(RomanNumeral::seventh_chord(degree, quality, inversion));
+                }
+            }
+            // A triad has no third inversion.
+            for inversion in &inversions[..3] {
+                numerals.push(RomanNumeral::major_chord(degree, *inversion));
+                numerals.push(RomanNumeral::minor_chord(degree, *inversion));
+                numerals.push(RomanNumeral::borrowed(degree, *inversion));
+                for quality in qualities {
+                    numerals.push(RomanNumeral::triad(degree, quality, *inversion));
+                }
+            }
+        }
+        for inversion in &inversions[..3] {
+            numerals.push(RomanNumeral::neapolitan(*inversion));
+            numerals.push(RomanNumeral {
+                inversion: *inversion,
+                ..RomanNumeral::cadential_six_four()
+            });
+        }
+        for named in [
+            NamedChord::ItalianSixth,
+            NamedChord::FrenchSixth,
+            NamedChord::GermanSixth,
+        ] {
+            let sixth = RomanNumeral::augmented_sixth(named).unwrap();
+            let size = if named == NamedChord::ItalianSixth {
+                3
+            } else {
+                4
+            };
+            for inversion in &inversions[..size] {
+                numerals.push(RomanNumeral {
+                    inversion: *inversion,
+                    ..sixth.clone()
+                });
+            }
+        }
+        for alteration in [Accidental::Natural, Accidental::DoubleSharp] {
+            numerals.push(RomanNumeral {
+                alteration,
+                ..RomanNumeral::major_chord(4, Inversion::ROOT)
+            });
+        }
+        let applied = numerals
+            .iter()
+            .map(|numeral| {
+                RomanNumeral::major_chord(5, Inversion::ROOT).with_secondary(numeral.clone())
+            })
+            .collect::<Vec<_>>();
+        numerals.extend(applied);
+        for numeral in numerals {
+            let s = numeral.to_string();
+            assert_eq!(s.parse::<RomanNumeral>(), Ok(numeral), "{}", s);
+        }
+        assert_eq!(RomanNumeral::neapolitan(Inversion::ROOT).to_string(), "N53");
+        let german = RomanNumeral {
+            inversion: Inversion::FIRST,
+            ..RomanNumeral::augmented_sixth(NamedChord::GermanSixth).unwrap()
+        };
+        assert_eq!(german.to_string(), "Ger+6b");
+        // Degrees outside the scale wrap around rather than failing.
+        let ninth = RomanNumeral::triad(9, Tonality::Minor, Inversion::ROOT);
+        assert_eq!(ninth.to_string(), "ii");
+        assert_eq!(
+            RomanNumeral::triad(0, Tonality::Major, Inversion::ROOT).to_string(),
+            "VII"
+        );
+    }
+
+    #[test]
+    fn test_additional_note_alteration() {
+        // The quality of an additional note alters the scale note rather than replacing its accidental,
+        // so a flat seventh over I in D major is C natural rather than C flat.
+        let key = Key::new_major(Note::new(Alphabet::D, Natural, 4)).unwrap();
+        let numeral = RomanNumeral {
+            additional_notes: vec![crate::ScaleDegree::new(7, Accidental::Flat)],
+            ..RomanNumeral::major_chord(1, Inversion::ROOT)
+        };
+        let seventh = numeral.chord(&key).notes[3];
+        assert_eq!(
+            (seventh.alphabet, seventh.accidental),
+            (Alphabet::C, Natural)
+        );
+        // Natural leaves the scale note alone, like no accidental at all.
+        let numeral = RomanNumeral {
+            additional_notes: vec![crate::ScaleDegree::new(7, Natural)],
+            ..RomanNumeral::major_chord(1, Inversion::ROOT)
+        };
+        let seventh = numeral.chord(&key).notes[3];
+        assert_eq!(
+            (seventh.alphabet, seventh.accidental),
+            (Alphabet::C, Accidental::Sharp)
+        );
+    }
+
+    #[test]
+    fn test_sevenths() {
+        let key = Key::new_major(Note::new(Alphabet::C, Natural, 4)).unwrap();
+        let fully: RomanNumeral = "viio7".parse().unwrap();
+        let chord = fully.chord(&key);
+        assert_eq!(chord.notes[3], Note::new(Alphabet::A, Accidental::Flat, 5));
+        let half: RomanNumeral = "viiø7".parse().unwrap();
+        let chord = half.chord(&key);
+        assert_eq!(chord.notes[3], Note::new(Alphabet::A, Natural, 5));
+        let key = Key::new_minor(Note::new(Alphabet::A, Natural, 4)).unwrap();
+        let chord = fully.chord(&key);
+        assert_eq!(chord.notes[0], Note::new(Alphabet::G, Accidental::Sharp, 4));
+        assert_eq!(chord.notes[3], Note::new(Alphabet::F, Natural, 4));
+    }
+
+    #[test]
+    fn test_applied() {
+        let key = Key::new_major(Note::new(Alphabet::C, Natural, 4)).unwrap();
+        let chord = "viio7/ii".parse::<RomanNumeral>().unwrap().chord(&key);
+        let expected = [
+            (Alphabet::C, Accidental::Sharp),
+            (Alphabet::E, Natural),
+            (Alphabet::G, Natural),
+            (Alphabet::B, Accidental::Flat),
+        ];
+        for (note, (alphabet, accidental)) in chord.notes.iter().zip(expected) {
+            assert_eq!(note.alphabet, alphabet);
+            assert_eq!(note.accidental, accidental);
+        }
+
+        let chord = "IV/IV".parse::<RomanNumeral>().unwrap().chord(&key);
+        assert_eq!(chord.notes[0].alphabet, Alphabet::B);
+        assert_eq!(chord.notes[0].accidental, Accidental::Flat);
+
+        let chord = "V/V/V".parse::<RomanNumeral>().unwrap().chord(&key);
+        assert_eq!(chord.notes[0].alphabet, Alphabet::A);
+        assert_eq!(chord.notes[1].accidental, Accidental::Sharp);
+
+        let chord = "V65/bVI".parse::<RomanNumeral>().unwrap().chord(&key);
+        assert_eq!(chord.notes[0].alphabet, Alphabet::G);
+        assert_eq!(chord.notes[0].accidental, Natural);
+        assert_eq!(chord.notes.len(), 4);
+
+        let key = Key::new_minor(Note::new(Alphabet::A, Natural, 4)).unwrap();
+        let chord = "V7/iv".parse::<RomanNumeral>().unwrap().chord(&key);
+        assert_eq!(chord.notes[1].accidental, Accidental::Sharp);
+        assert_eq!(chord.notes[3].alphabet, Alphabet::G);
+        assert_eq!(chord.notes[3].accidental, Natural);
+    }
+
+    #[test]
+    fn test_applied_unusual_key() {
+        let key = Key::new_major(Note::new(Alphabet::C, Accidental::Sharp, 4)).unwrap();
+        let chord = "V/V".parse::<RomanNumeral>().unwrap().chord(&key);
+        // D# major has no key signature, so the chord is spelled in E-flat major.
+        assert_eq!(chord.notes[0].alphabet, Alphabet::E);
+        assert_eq!(chord.notes[0].accidental, Accidental::Flat);
+        assert_eq!(
+            chord.notes[1].id().simple(),
+            Note::new(Alphabet::F, Accidental::DoubleSharp, 4)
+                .id()
+                .simple()
+        );
+    }
+
+    fn spelling(chord: &crate::chord::Chord) -> Vec<(Alphabet, Accidental)> {
+        chord
+            .notes
+            .iter()
+            .map(|note| (note.alphabet, note.accidental))
+            .collect()
+    }
+
+    #[test]
+    fn test_chromatic() {
+        use Accidental::{Flat, Sharp};
+        let c_major = Key::new_major(Note::new(Alphabet::C, Natural, 4)).unwrap();
+        let a_minor = Key::new_minor(Note::new(Alphabet::A, Natural, 4)).unwrap();
+
+        let neapolitan = RomanNumeral::neapolitan(Inversion::ROOT);
+        assert_eq!(
+            spelling(&neapolitan.chord(&c_major)),
+            vec![
+                (Alphabet::D, Flat),
+                (Alphabet::F, Natural),
+                (Alphabet::A, Flat)
+            ]
+        );
+        assert_eq!(
+            spelling(&neapolitan.chord(&a_minor)),
+            vec![
+                (Alphabet::B, Flat),
+                (Alphabet::D, Natural),
+                (Alphabet::F, Natural)
+            ]
+        );
+
+        let italian = RomanNumeral::augmented_sixth(NamedChord::ItalianSixth).unwrap();
+        assert_eq!(
+            spelling(&italian.chord(&c_major)),
+            vec![
+                (Alphabet::A, Flat),
+                (Alphabet::C, Natural),
+                (Alphabet::F, Sharp)
+            ]
+        );
+        let french = RomanNumeral::augmented_sixth(NamedChord::FrenchSixth).unwrap();
+        assert_eq!(
+            spelling(&french.chord(&c_major)),
+            vec![
+                (Alphabet::A, Flat),
+                (Alphabet::C, Natural),
+                (Alphabet::D, Natural),
+                (Alphabet::F, Sharp)
+            ]
+        );
+        let german = "Ger+6/V".parse::<RomanNumeral>().unwrap();
+        assert_eq!(
+            spelling(&german.chord(&c_major)),
+            vec![
+                (Alphabet::E, Flat),
+                (Alphabet::G, Natural),
+                (Alphabet::B, Flat),
+                (Alphabet::C, Sharp)
+            ]
+        );
+        assert!(RomanNumeral::augmented_sixth(NamedChord::Neapolitan).is_none());
+
+        let cadential = RomanNumeral::cadential_six_four();
+        assert_eq!(
+            spelling(&cadential.chord(&a_minor)),
+            vec![
+                (Alphabet::E, Natural),
+                (Alphabet::A, Natural),
+                (Alphabet::C, Natural)
+            ]
+        );
+
+        assert_eq!(
+            spelling(&RomanNumeral::borrowed(4, Inversion::ROOT).chord(&c_major)),
+            vec![
+                (Alphabet::F, Natural),
+                (Alphabet::A, Flat),
+                (Alphabet::C, Natural)
+            ]
+        );
+        assert_eq!(
+            spelling(&RomanNumeral::borrowed(7, Inversion::ROOT).chord(&c_major)),
+            vec![
+                (Alphabet::B, Flat),
+                (Alphabet::D, Natural),
+                (Alphabet::F, Natural)
+            ]
+        );
+        assert_eq!(
+            RomanNumeral::borrowed(2, Inversion::ROOT).to_string(),
+            "iio"
+        );
+    }
 }
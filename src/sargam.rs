@@ -0,0 +1,251 @@
+//! Indian sargam, where the notes of the scale are sung Sa Re Ga Ma Pa Dha Ni.
+
+use crate::note::Note;
+use crate::pitch::{RelativePitch, RelativeSystem};
+use crate::solfege::{
+    octaves_above, parse_octave_marks, shift_octaves, spell_major_degree, write_octave_marks,
+};
+use crate::Interval;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The seven swaras.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Swara {
+    Sa,
+    Re,
+    Ga,
+    Ma,
+    Pa,
+    Dha,
+    Ni,
+}
+
+impl Swara {
+    pub const ALL: [Self; 7] = [
+        Self::Sa,
+        Self::Re,
+        Self::Ga,
+        Self::Ma,
+        Self::Pa,
+        Self::Dha,
+        Self::Ni,
+    ];
+
+    /// The position of the swara in the scale, from 0 for Sa to 6 for Ni.
+    pub const fn index(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The half steps of the shuddha (natural) swara above Sa.
+    const fn half_steps(&self) -> u8 {
+        match self {
+            Self::Sa => 0,
+            Self::Re => 2,
+            Self::Ga => 4,
+            Self::Ma => 5,
+            Self::Pa => 7,
+            Self::Dha => 9,
+            Self::Ni => 11,
+        }
+    }
+
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Sa => "Sa",
+            Self::Re => "Re",
+            Self::Ga => "Ga",
+            Self::Ma => "Ma",
+            Self::Pa => "Pa",
+            Self::Dha => "Dha",
+            Self::Ni => "Ni",
+        }
+    }
+}
+
+/// The form of a swara.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwaraVariant {
+    /// The natural form.
+    #[default]
+    Shuddha,
+    /// A half step lower, for Re, Ga, Dha and Ni.
+    Komal,
+    /// A half step higher, for Ma.
+    Tivra,
+}
+
+/// A note in sargam, relative to Sa.
+///
+/// Only Re, Ga, Dha and Ni can be komal and only Ma can be tivra, so every pitch has exactly one name.
+/// Notes are written "komal Re" and "tivra Ma", and the common shorthand where lowercase
+/// r g d n are komal and uppercase M is tivra is also accepted when parsing.
+/// Octaves are written with `'` for the upper (taar) octave and `,` for the lower (mandra) octave.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::sargam::{Sargam, Swara, SwaraVariant};
+/// let sa = Note::new(Alphabet::A, Accidental::Natural, 4);
+/// let note = Note::new(Alphabet::F, Accidental::Natural, 4);
+/// let sargam = Sargam::from_note(&note, &sa);
+/// assert_eq!(sargam, Sargam::new(Swara::Dha, SwaraVariant::Komal, 0).unwrap());
+/// assert_eq!(sargam.to_string(), "komal Dha");
+/// assert_eq!("d".parse::<Sargam>(), Ok(sargam));
+/// assert_eq!(sargam.to_note(&sa).unwrap().alphabet, Alphabet::F);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sargam {
+    pub swara: Swara,
+    pub variant: SwaraVariant,
+    /// The number of octaves above the middle (madhya) octave.
+    pub octave: i8,
+}
+
+impl Sargam {
+    /// Creates a note in sargam, returning `None` if the swara does not take the variant.
+    pub const fn new(swara: Swara, variant: SwaraVariant, octave: i8) -> Option<Self> {
+        match (swara, variant) {
+            (_, SwaraVariant::Shuddha)
+            | (Swara::Re | Swara::Ga | Swara::Dha | Swara::Ni, SwaraVariant::Komal)
+            | (Swara::Ma, SwaraVariant::Tivra) => Some(Self {
+                swara,
+                variant,
+                octave,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Finds the name of a note's pitch when Sa is `sa`.
+    pub fn from_note(note: &Note, sa: &Note) -> Self {
+        let half_steps = (note.id().0 - sa.id().0).rem_euclid(12);
+        let (swara, variant) = match half_steps {
+            0 => (Swara::Sa, SwaraVariant::Shuddha),
+            1 => (Swara::Re, SwaraVariant::Komal),
+            2 => (Swara::Re, SwaraVariant::Shuddha),
+            3 => (Swara::Ga, SwaraVariant::Komal),
+            4 => (Swara::Ga, SwaraVariant::Shuddha),
+            5 => (Swara::Ma, SwaraVariant::Shuddha),
+            6 => (Swara::Ma, SwaraVariant::Tivra),
+            7 => (Swara::Pa, SwaraVariant::Shuddha),
+            8 => (Swara::Dha, SwaraVariant::Komal),
+            9 => (Swara::Dha, SwaraVariant::Shuddha),
+            10 => (Swara::Ni, SwaraVariant::Komal),
+            _ => (Swara::Ni, SwaraVariant::Shuddha),
+        };
+        Self {
+            swara,
+            variant,
+            octave: octaves_above(sa, note, half_steps),
+        }
+    }
+
+    /// Spells the note when Sa is `sa`, using the letter of the swara so komal Re above C is D♭.
+    pub fn to_note(&self, sa: &Note) -> Option<Note> {
+        let alteration = match self.variant {
+            SwaraVariant::Shuddha => 0,
+            SwaraVariant::Komal => -1,
+            SwaraVariant::Tivra => 1,
+        };
+        let note = spell_major_degree(sa, self.swara.index(), alteration)?;
+        shift_octaves(note, self.octave)
+    }
+
+    /// The number of half steps above Sa.
+    pub const fn half_steps(&self) -> i16 {
+        let alteration = match self.variant {
+            SwaraVariant::Shuddha => 0,
+            SwaraVariant::Komal => -1,
+            SwaraVariant::Tivra => 1,
+        };
+        self.swara.half_steps() as i16 + alteration + self.octave as i16 * 12
+    }
+}
+
+impl RelativeSystem for Sargam {
+    fn root() -> Self {
+        Self {
+            swara: Swara::Sa,
+            variant: SwaraVariant::Shuddha,
+            octave: 0,
+        }
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new(second.half_steps() - first.half_steps())
+    }
+}
+
+impl Display for Sargam {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.variant {
+            SwaraVariant::Shuddha => {}
+            SwaraVariant::Komal => write!(f, "komal ")?,
+            SwaraVariant::Tivra => write!(f, "tivra ")?,
+        }
+        write!(f, "{}", self.swara.name())?;
+        write_octave_marks(f, self.octave)
+    }
+}
+
+impl FromStr for Sargam {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, octave) = parse_octave_marks(s.trim());
+        let (variant, name) = if let Some(name) = body.strip_prefix("komal ") {
+            (SwaraVariant::Komal, name)
+        } else if let Some(name) = body.strip_prefix("tivra ") {
+            (SwaraVariant::Tivra, name)
+        } else {
+            match body {
+                "S" => (SwaraVariant::Shuddha, "Sa"),
+                "r" => (SwaraVariant::Komal, "Re"),
+                "R" => (SwaraVariant::Shuddha, "Re"),
+                "g" => (SwaraVariant::Komal, "Ga"),
+                "G" => (SwaraVariant::Shuddha, "Ga"),
+                "m" => (SwaraVariant::Shuddha, "Ma"),
+                "M" => (SwaraVariant::Tivra, "Ma"),
+                "P" => (SwaraVariant::Shuddha, "Pa"),
+                "d" => (SwaraVariant::Komal, "Dha"),
+                "D" => (SwaraVariant::Shuddha, "Dha"),
+                "n" => (SwaraVariant::Komal, "Ni"),
+                "N" => (SwaraVariant::Shuddha, "Ni"),
+                name => (SwaraVariant::Shuddha, name),
+            }
+        };
+        let swara = Swara::ALL
+            .into_iter()
+            .find(|swara| swara.name().eq_ignore_ascii_case(name))
+            .ok_or(())?;
+        Self::new(swara, variant, octave).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Alphabet};
+
+    #[test]
+    fn test_sargam() {
+        let sa = Note::new(Alphabet::D, Accidental::Natural, 4);
+        for half_steps in -12..24 {
+            let note = sa.increment_by(half_steps);
+            let sargam = Sargam::from_note(&note, &sa);
+            assert_eq!(sargam.half_steps(), half_steps as i16);
+            assert_eq!(sargam.to_note(&sa).unwrap(), note);
+            assert_eq!(sargam.to_string().parse::<Sargam>(), Ok(sargam));
+        }
+        assert!("komal Pa".parse::<Sargam>().is_err());
+        assert!("tivra Ga".parse::<Sargam>().is_err());
+    }
+}
@@ -2,12 +2,16 @@ use crate::key::{Key, Scale};
 use crate::note::Note;
 use crate::{Accidental, Interval};
 use std::num::NonZeroU8;
+use std::ops::{Add, Sub};
 use crate::pitch::{RelativePitch, RelativeSystem};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScaleDegree {
     pub degree: NonZeroU8,
+    /// The alteration of the degree relative to the scale, so a flat on a sharp scale note gives a natural.
+    ///
+    /// [`Accidental::None`] and [`Accidental::Natural`] both leave the scale note unaltered.
     pub quality: Accidental,
 }
 
@@ -19,14 +23,115 @@ impl ScaleDegree {
         }
     }
 
+    /// Finds the degree of a note in a key.
+    ///
+    /// In a diatonic key the degree is chosen by the letter of the note,
+    /// and notes outside the scale are given an altered degree, like ♯4 or ♭7.
+    /// In a chromatic key the degree is the position of the pitch in the chromatic scale.
+    /// Returns `None` if the alteration would be more than a double sharp or flat.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_major(Note::new(Alphabet::C, Accidental::Natural, 4)).unwrap();
+    /// let note = Note::new(Alphabet::F, Accidental::Sharp, 4);
+    /// assert_eq!(ScaleDegree::from_note(&note, &key), Some(ScaleDegree::new(4, Accidental::Sharp)));
+    /// let note = Note::new(Alphabet::B, Accidental::Flat, 4);
+    /// assert_eq!(ScaleDegree::from_note(&note, &key), Some(ScaleDegree::new(7, Accidental::Flat)));
+    /// ```
     pub fn from_note(note: &Note, key: &Key) -> Option<Self> {
         let scale = Scale::from(key);
-        scale
+        if let Key::Chromatic = key {
+            return scale
+                .notes
+                .iter()
+                .position(|n| n.id().simple() == note.id().simple())
+                .map(|i| Self::new(i as u8 + 1, Accidental::None));
+        }
+        let (i, scale_note) = scale
             .notes
             .iter()
             .enumerate()
-            .find(|(_, &n)| n.id().simple() == note.id().simple())
-            .map(|(i, _)| Self::new(i as u8 + 1, Accidental::None))
+            .find(|(_, n)| n.alphabet == note.alphabet)?;
+        let offset = (note.id().0 - scale_note.id().0).rem_euclid(12) as i8;
+        // Wrap to the nearest alteration, so B♭ against B is -1 and not 11.
+        let offset = if offset > 6 { offset - 12 } else { offset };
+        let quality = match Accidental::from_offset(offset)? {
+            Accidental::Natural => Accidental::None,
+            quality => quality,
+        };
+        Some(Self::new(i as u8 + 1, quality))
+    }
+
+    /// Spells the degree in a key, applying its alteration to the scale note.
+    ///
+    /// Returns `None` if the degree is outside the scale or the alteration cannot be spelled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let key = Key::new_major(Note::new(Alphabet::D, Accidental::Natural, 4)).unwrap();
+    /// let note = ScaleDegree::new(3, Accidental::Flat).to_note(&key).unwrap();
+    /// assert_eq!(note.alphabet, Alphabet::F);
+    /// assert_eq!(note.accidental, Accidental::Natural);
+    /// ```
+    pub fn to_note(&self, key: &Key) -> Option<Note> {
+        let scale = Scale::from(key);
+        let note = scale.notes.get(self.degree.get() as usize - 1)?;
+        match key {
+            Key::Chromatic => Some(note.increment_by(self.quality.offset() as i64)),
+            Key::Diatonic { .. } => note.altered(self.quality.offset()),
+        }
+    }
+
+    /// Moves the degree by a number of diatonic steps, wrapping around the octave and keeping its alteration.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let degree = ScaleDegree::new(6, Accidental::Flat);
+    /// assert_eq!(degree.shift(3), ScaleDegree::new(2, Accidental::Flat));
+    /// assert_eq!(degree + 1, ScaleDegree::new(7, Accidental::Flat));
+    /// assert_eq!(ScaleDegree::new(2, Accidental::None) - ScaleDegree::new(7, Accidental::None), 2);
+    /// ```
+    pub const fn shift(&self, steps: i64) -> Self {
+        let degree = (self.degree.get() as i64 - 1 + steps).rem_euclid(7) as u8 + 1;
+        Self::new(degree, self.quality)
+    }
+
+    /// Returns the number of diatonic steps from `other` up to `self`, between 0 and 6.
+    pub const fn steps_from(&self, other: &Self) -> u8 {
+        (self.degree.get() as i16 - other.degree.get() as i16).rem_euclid(7) as u8
+    }
+
+    /// Whether the degree is altered from the scale note.
+    pub const fn is_chromatic(&self) -> bool {
+        !matches!(self.quality, Accidental::None | Accidental::Natural)
+    }
+}
+
+impl Add<i64> for ScaleDegree {
+    type Output = Self;
+
+    fn add(self, rhs: i64) -> Self::Output {
+        self.shift(rhs)
+    }
+}
+
+impl Sub<i64> for ScaleDegree {
+    type Output = Self;
+
+    fn sub(self, rhs: i64) -> Self::Output {
+        self.shift(-rhs)
+    }
+}
+
+impl Sub for ScaleDegree {
+    type Output = u8;
+
+    /// The number of diatonic steps from `rhs` up to `self`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.steps_from(&rhs)
     }
 }
 
@@ -55,3 +160,31 @@ impl Default for ScaleDegree {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alphabet;
+
+    #[test]
+    fn test_chromatic_round_trip() {
+        for key in [
+            Key::new_major(Note::new(Alphabet::E, Accidental::Flat, 4)).unwrap(),
+            Key::new_minor(Note::new(Alphabet::F, Accidental::Sharp, 4)).unwrap(),
+        ] {
+            for alphabet in (0..7).map(Alphabet::from_index) {
+                for accidental in [Accidental::Flat, Accidental::Natural, Accidental::Sharp] {
+                    let note = Note::new(alphabet, accidental, 4);
+                    let degree = ScaleDegree::from_note(&note, &key).unwrap();
+                    let spelled = degree.to_note(&key).unwrap();
+                    assert_eq!(spelled.alphabet, note.alphabet);
+                    assert_eq!(spelled.accidental, note.accidental);
+                }
+            }
+        }
+        let key = Key::new_chromatic();
+        let note = Note::new(Alphabet::D, Accidental::Sharp, 4);
+        let degree = ScaleDegree::from_note(&note, &key).unwrap();
+        assert_eq!(degree.to_note(&key).unwrap().id().simple(), note.id().simple());
+    }
+}
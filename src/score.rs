@@ -1,4 +1,6 @@
 use crate::part::Part;
+use crate::time::TempoMap;
+use crate::TimeSignature;
 use std::fmt::Display;
 
 #[derive(Clone)]
@@ -73,6 +75,22 @@ pub struct Score {
     pub ident: Option<ScoreIdentification>,
     pub credits: Vec<ScoreCredit>,
     pub parts: Vec<Part>,
+    pub tempo: TempoMap,
+}
+
+impl Score {
+    /// The time signature of each measure, taken from the first part.
+    pub fn time_signatures(&self) -> Vec<TimeSignature> {
+        self.parts
+            .first()
+            .map(|part| {
+                part.measures
+                    .iter()
+                    .map(|measure| measure.time_signature.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -1,8 +1,12 @@
 //! Solfège is a system of attributing a distinct syllable to each note in a musical scale.
 
+use crate::key::Key;
+use crate::measure::TimedMeasureItemInner;
 use crate::note::Note;
+use crate::part::Part;
 use crate::pitch::{Pitch, RelativePitch};
-use crate::{Accidental, Alphabet};
+use crate::{Accidental, Alphabet, Tonality};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 
 /// This enum represents all possible syllables of the solfège system.
@@ -84,6 +88,88 @@ impl SolfegeSyllable {
     }
 }
 
+impl SolfegeSyllable {
+    /// The syllables of the major scale, starting from Do.
+    pub const DIATONIC: [Self; 7] = [
+        Self::Do,
+        Self::Re,
+        Self::Mi,
+        Self::Fa,
+        Self::So,
+        Self::La,
+        Self::Ti,
+    ];
+
+    /// The number of letter steps from Do, between 0 and 6.
+    pub const fn degree(&self) -> u8 {
+        match self {
+            Self::Do | Self::Di => 0,
+            Self::Ra | Self::Re | Self::Ri => 1,
+            Self::Me | Self::Mi => 2,
+            Self::Fa | Self::Fi => 3,
+            Self::Se | Self::So | Self::Si => 4,
+            Self::Le | Self::La | Self::Li => 5,
+            Self::Te | Self::Ti => 6,
+        }
+    }
+
+    /// The alteration of the syllable in half steps, relative to the major scale.
+    pub const fn alteration(&self) -> i8 {
+        match self {
+            Self::Di | Self::Ri | Self::Fi | Self::Si | Self::Li => 1,
+            Self::Ra | Self::Me | Self::Se | Self::Le | Self::Te => -1,
+            _ => 0,
+        }
+    }
+
+    /// Finds the syllable for a letter step from Do and an alteration of the major scale note.
+    ///
+    /// Returns `None` for alterations without a syllable, like a raised Mi.
+    pub const fn from_degree(degree: u8, alteration: i8) -> Option<Self> {
+        match (degree % 7, alteration) {
+            (degree, 0) => Some(Self::DIATONIC[degree as usize]),
+            (0, 1) => Some(Self::Di),
+            (1, -1) => Some(Self::Ra),
+            (1, 1) => Some(Self::Ri),
+            (2, -1) => Some(Self::Me),
+            (3, 1) => Some(Self::Fi),
+            (4, -1) => Some(Self::Se),
+            (4, 1) => Some(Self::Si),
+            (5, -1) => Some(Self::Le),
+            (5, 1) => Some(Self::Li),
+            (6, -1) => Some(Self::Te),
+            _ => None,
+        }
+    }
+
+    /// Finds the syllable for a number of half steps above Do.
+    ///
+    /// Chromatic pitches take a raised syllable when the melody is ascending
+    /// and a lowered syllable otherwise, so a half step above Fa is Fi going up and Se going down.
+    pub const fn from_half_steps(half_steps: u8, direction: Ordering) -> Self {
+        let raised = matches!(direction, Ordering::Greater);
+        match half_steps % 12 {
+            0 => Self::Do,
+            1 if raised => Self::Di,
+            1 => Self::Ra,
+            2 => Self::Re,
+            3 if raised => Self::Ri,
+            3 => Self::Me,
+            4 => Self::Mi,
+            5 => Self::Fa,
+            6 if raised => Self::Fi,
+            6 => Self::Se,
+            7 => Self::So,
+            8 if raised => Self::Si,
+            8 => Self::Le,
+            9 => Self::La,
+            10 if raised => Self::Li,
+            10 => Self::Te,
+            _ => Self::Ti,
+        }
+    }
+}
+
 impl Display for SolfegeSyllable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -119,6 +205,47 @@ impl PartialEq for SolfegeSyllable {
 #[repr(transparent)]
 pub struct Moveable(pub Note);
 
+impl Moveable {
+    /// Finds the Do of a key.
+    ///
+    /// Minor keys either sing their tonic on Do or on La, depending on `minor`.
+    /// Returns `None` for a chromatic key.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::solfege::{MinorSolfege, Moveable};
+    /// let key = Key::new_minor(Note::new(Alphabet::E, Accidental::Natural, 4)).unwrap();
+    /// let Moveable(root) = Moveable::from_key(&key, MinorSolfege::LaBased).unwrap();
+    /// assert_eq!(root.alphabet, Alphabet::G);
+    /// ```
+    pub fn from_key(key: &Key, minor: MinorSolfege) -> Option<Self> {
+        match key {
+            Key::Chromatic => None,
+            Key::Diatonic { root, .. } => {
+                if key.tonality() == Some(Tonality::Minor) && minor == MinorSolfege::LaBased {
+                    root.increment_by(3)
+                        .respell(root.alphabet.shift(2))
+                        .map(Self)
+                } else {
+                    Some(Self(*root))
+                }
+            }
+        }
+    }
+}
+
+/// How to sing minor keys in movable solfège.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MinorSolfege {
+    /// The tonic is Do and the third is Me.
+    #[default]
+    DoBased,
+    /// The tonic is La, as in the relative major.
+    LaBased,
+}
+
 #[allow(non_upper_case_globals)]
 /// The fixed solfège root is just a movable solfège with a base of C.
 pub const Fixed: Moveable = Moveable(Note::new(Alphabet::C, Accidental::Natural, 4));
@@ -144,6 +271,199 @@ impl Solfege {
     pub const fn id(&self) -> RelativePitch {
         Pitch(self.kind.0.id().0 + self.syllable.into_u8() as i16).simple()
     }
+
+    /// Finds the syllable of a note.
+    ///
+    /// The syllable follows the spelling of the note where it can, so C♯ is Di and D♭ is Ra in C.
+    /// Notes whose spelling has no syllable, like E♯ in C, are named by pitch
+    /// with `direction` choosing between raised and lowered syllables.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::cmp::Ordering;
+    /// use note_pen::prelude::*;
+    /// use note_pen::solfege::Fixed;
+    /// let note = Note::new(Alphabet::G, Accidental::Flat, 4);
+    /// let solfege = Solfege::from_note(&note, Fixed, Ordering::Greater);
+    /// assert_eq!(solfege.syllable.to_string(), "Se");
+    /// ```
+    pub fn from_note(note: &Note, kind: Moveable, direction: Ordering) -> Self {
+        let root = kind.0;
+        let half_steps = (note.id().0 - root.id().0).rem_euclid(12) as u8;
+        let (degree, alteration) = major_degree(&root, note);
+        let syllable = SolfegeSyllable::from_degree(degree, alteration)
+            .unwrap_or(SolfegeSyllable::from_half_steps(half_steps, direction));
+        Self::new(syllable, kind)
+    }
+
+    /// Spells the syllable as a note in the octave starting from the root.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::solfege::Moveable;
+    /// let root = Moveable(Note::new(Alphabet::E, Accidental::Flat, 4));
+    /// let note = Solfege::new(SolfegeSyllable::Fi, root).note().unwrap();
+    /// assert_eq!(note.alphabet, Alphabet::A);
+    /// assert_eq!(note.accidental, Accidental::Natural);
+    /// ```
+    pub fn note(&self) -> Option<Note> {
+        spell_major_degree(
+            &self.kind.0,
+            self.syllable.degree(),
+            self.syllable.alteration(),
+        )
+    }
+}
+
+/// Finds the letter steps of `note` above `root`, between 0 and 6,
+/// and its alteration in half steps from the major scale of `root`.
+pub(crate) fn major_degree(root: &Note, note: &Note) -> (u8, i8) {
+    let half_steps = (note.id().0 - root.id().0).rem_euclid(12) as i8;
+    let degree = (note.alphabet.index() as i16 - root.alphabet.index() as i16).rem_euclid(7) as u8;
+    let natural = SolfegeSyllable::DIATONIC[degree as usize].into_u8() as i8;
+    (degree, (half_steps - natural + 6).rem_euclid(12) - 6)
+}
+
+/// Spells a degree of the major scale of `root` in the octave starting from `root`.
+pub(crate) fn spell_major_degree(root: &Note, degree: u8, alteration: i8) -> Option<Note> {
+    let natural = SolfegeSyllable::DIATONIC[degree as usize % 7].into_u8() as i64;
+    root.increment_by(natural + alteration as i64)
+        .respell(root.alphabet.shift(degree as i64))
+}
+
+/// Names each note of a melody, choosing chromatic syllables by spelling and then by melodic direction.
+///
+/// A chromatic note is raised when the melody moves up from it and lowered when it moves down,
+/// falling back to the motion into the note at the end of the melody and on repeated notes.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::solfege::{transcribe, Fixed};
+/// let melody = ["C4", "E4", "G4", "C5"].map(|note| note.parse::<Note>().unwrap());
+/// let syllables = transcribe(&melody, Fixed)
+///     .iter()
+///     .map(|solfege| solfege.syllable.to_string())
+///     .collect::<Vec<_>>();
+/// assert_eq!(syllables, ["Do", "Mi", "So", "Do"]);
+/// ```
+pub fn transcribe(notes: &[Note], kind: Moveable) -> Vec<Solfege> {
+    (0..notes.len())
+        .map(|i| Solfege::from_note(&notes[i], kind, direction(notes, i)))
+        .collect()
+}
+
+/// Names each note of a melody by pitch alone, ignoring how chromatic notes are spelled.
+///
+/// This suits melodies read from MIDI, where every black key is spelled as a sharp.
+pub fn transcribe_by_direction(notes: &[Note], kind: Moveable) -> Vec<Solfege> {
+    (0..notes.len())
+        .map(|i| {
+            let half_steps = (notes[i].id().0 - kind.0.id().0).rem_euclid(12) as u8;
+            let syllable = SolfegeSyllable::from_half_steps(half_steps, direction(notes, i));
+            Solfege::new(syllable, kind)
+        })
+        .collect()
+}
+
+/// Names the melody of a part, taking the top note of each chord and skipping rests.
+///
+/// When `kind` is `None`, Do is found from the key of each measure.
+pub fn transcribe_part(part: &Part, kind: Option<Moveable>, minor: MinorSolfege) -> Vec<Solfege> {
+    let mut solfege = vec![];
+    for measure in part.measures.iter() {
+        let Some(kind) = kind.or_else(|| Moveable::from_key(&measure.key, minor)) else {
+            continue;
+        };
+        let notes = measure
+            .notes
+            .iter()
+            .filter_map(|item| match &item.item {
+                TimedMeasureItemInner::Note(note) => Some(*note),
+                TimedMeasureItemInner::Chord(chord) => {
+                    chord.notes.iter().max_by_key(|note| note.id()).copied()
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        solfege.extend(transcribe(&notes, kind));
+    }
+    solfege
+}
+
+/// Spells a sung melody, placing each note in the octave nearest the previous one.
+///
+/// The first note is placed in the octave starting from its root.
+/// Returns `None` if a syllable cannot be spelled from its root.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::solfege::{realize, Fixed};
+/// let melody = [SolfegeSyllable::Do, SolfegeSyllable::Ti, SolfegeSyllable::Do]
+///     .map(|syllable| Solfege::new(syllable, Fixed));
+/// let notes = realize(&melody).unwrap();
+/// assert!(notes[1].id() < notes[0].id());
+/// assert_eq!(notes[2], notes[0]);
+/// ```
+pub fn realize(syllables: &[Solfege]) -> Option<Vec<Note>> {
+    let mut notes: Vec<Note> = vec![];
+    for solfege in syllables {
+        let mut note = solfege.note()?;
+        if let Some(previous) = notes.last() {
+            let distance = (note.id().0 - previous.id().0).rem_euclid(12);
+            let target = previous.id().0
+                + if distance > 6 {
+                    distance - 12
+                } else {
+                    distance
+                };
+            let octaves = (target - note.id().0) / 12;
+            note.octave = (note.octave as i16 + octaves) as u8;
+        }
+        notes.push(note);
+    }
+    Some(notes)
+}
+
+/// The number of octaves from `root` to `note`, where `half_steps` is the pitch of `note`'s degree above `root`.
+pub(crate) const fn octaves_above(root: &Note, note: &Note, half_steps: i16) -> i8 {
+    (note.id().0 - root.id().0 - half_steps).div_euclid(12) as i8
+}
+
+/// Moves a note by whole octaves, returning `None` if it leaves the range of [`Note::octave`].
+pub(crate) fn shift_octaves(note: Note, octaves: i8) -> Option<Note> {
+    let octave = u8::try_from(note.octave as i16 + octaves as i16).ok()?;
+    Some(Note { octave, ..note })
+}
+
+/// Writes `'` for each octave up and `,` for each octave down.
+pub(crate) fn write_octave_marks(f: &mut Formatter<'_>, octave: i8) -> std::fmt::Result {
+    let mark = if octave > 0 { "'" } else { "," };
+    write!(f, "{}", mark.repeat(octave.unsigned_abs() as usize))
+}
+
+/// Splits octave marks written with [`write_octave_marks`] off the end of a string.
+pub(crate) fn parse_octave_marks(s: &str) -> (&str, i8) {
+    let body = s.trim_end_matches(['\'', ',']);
+    let marks = &s[body.len()..];
+    let up = marks.chars().filter(|&c| c == '\'').count() as i8;
+    let down = marks.chars().filter(|&c| c == ',').count() as i8;
+    (body, up - down)
+}
+
+/// The direction of the melody leaving the note at `i`, or entering it if the melody does not move on.
+fn direction(notes: &[Note], i: usize) -> Ordering {
+    let leaving = notes
+        .get(i + 1)
+        .map(|next| next.id().cmp(&notes[i].id()))
+        .filter(|ordering| ordering.is_ne());
+    let entering = i
+        .checked_sub(1)
+        .map(|previous| notes[i].id().cmp(&notes[previous].id()))
+        .filter(|ordering| ordering.is_ne());
+    leaving.or(entering).unwrap_or(Ordering::Greater)
 }
 
 #[cfg(test)]
@@ -165,4 +485,53 @@ mod tests {
             SolfegeSyllable::Ti.into_u8()
         );
     }
+
+    #[test]
+    fn test_chromatic_syllables() {
+        let note = |s: &str| s.parse::<Note>().unwrap();
+        let syllables = |solfege: Vec<Solfege>| {
+            solfege
+                .iter()
+                .map(|solfege| solfege.syllable.to_string())
+                .collect::<Vec<_>>()
+        };
+        let d_major = Moveable(note("D4"));
+        let melody = [note("D4"), note("D#4"), note("E4"), note("Eb4"), note("D4")];
+        assert_eq!(
+            syllables(transcribe(&melody, d_major)),
+            ["Do", "Di", "Re", "Ra", "Do"]
+        );
+        // The same pitches from MIDI are named by direction instead.
+        let melody = [note("D4"), note("D#4"), note("E4"), note("D#4"), note("D4")];
+        assert_eq!(
+            syllables(transcribe_by_direction(&melody, d_major)),
+            ["Do", "Di", "Re", "Ra", "Do"]
+        );
+        let e_sharp = [note("C4"), note("E#4"), note("F#4")];
+        assert_eq!(syllables(transcribe(&e_sharp, Fixed)), ["Do", "Fa", "Fi"]);
+    }
+
+    #[test]
+    fn test_minor() {
+        let a_minor = Key::new_minor(Note::new(Alphabet::A, Accidental::Natural, 4)).unwrap();
+        let melody = [Alphabet::A, Alphabet::C, Alphabet::E]
+            .map(|alphabet| Note::new(alphabet, Accidental::Natural, 4));
+        for (minor, expected) in [
+            (MinorSolfege::DoBased, ["Do", "Me", "So"]),
+            (MinorSolfege::LaBased, ["La", "Do", "Mi"]),
+        ] {
+            let kind = Moveable::from_key(&a_minor, minor).unwrap();
+            let solfege = transcribe(&melody, kind);
+            let names = solfege
+                .iter()
+                .map(|solfege| solfege.syllable.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(names, expected);
+            let notes = realize(&solfege).unwrap();
+            for (note, expected) in notes.iter().zip(melody.iter()) {
+                assert_eq!(note.alphabet, expected.alphabet);
+                assert_eq!(note.accidental, expected.accidental);
+            }
+        }
+    }
 }
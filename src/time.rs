@@ -1,27 +1,297 @@
 //! Time-related types for scores including relative time and speed.
 
-use crate::duration::PrimitiveDuration;
+use crate::duration::{Duration, Fraction, PrimitiveDuration};
+use crate::TimeSignature;
+use std::fmt::Display;
+use std::ops::Add;
 
-#[derive(Debug, Clone, Copy)]
+/// A tempo as a number of beats per minute, where the beat can be any duration,
+/// like a dotted quarter in 6/8.
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[repr(transparent)]
 pub struct Tempo {
     pub bpm: f64,
+    pub beat: Duration,
 }
 
 impl Tempo {
+    /// Creates a tempo counted in quarter notes.
     #[inline]
     pub const fn new(bpm: f64) -> Self {
-        Self { bpm }
+        Self::with_beat(bpm, Duration::QUARTER)
+    }
+
+    /// Creates a tempo counted in the given beat.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::time::Tempo;
+    /// let dotted_quarter = Tempo::with_beat(60.0, Duration::new(PrimitiveDuration::QUARTER, 1));
+    /// assert_eq!(dotted_quarter.whole_notes_per_second(), Tempo::new(90.0).whole_notes_per_second());
+    /// ```
+    #[inline]
+    pub const fn with_beat(bpm: f64, beat: Duration) -> Self {
+        Self { bpm, beat }
     }
 
     #[inline]
     pub const fn value(&self) -> f64 {
         self.bpm
     }
+
+    /// The speed in whole notes per second, which compares tempos with different beats.
+    #[inline]
+    pub fn whole_notes_per_second(&self) -> f64 {
+        self.bpm / 60.0 * self.beat.length().to_f64()
+    }
+
+    /// The tempo with the same beat that moves at `rate` whole notes per second.
+    fn with_rate(&self, rate: f64) -> Self {
+        Self::with_beat(rate * 60.0 / self.beat.length().to_f64(), self.beat)
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Default for Tempo {
+    /// 120 quarter notes per minute, the default tempo in MIDI.
+    fn default() -> Self {
+        Self::new(120.0)
+    }
+}
+
+/// A change in a [`TempoMap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TempoChange {
+    /// Switches to a new tempo immediately.
+    Set(Tempo),
+    /// Changes smoothly from the current tempo to `tempo` over `length`,
+    /// as in an accelerando or ritardando.
+    /// The tempo changes linearly with the position in the score.
+    Ramp { length: Fraction, tempo: Tempo },
+    /// Stops the music for a number of seconds, as with a fermata.
+    /// Place it at the end of the held note: the note lasts longer, and the music resumes afterwards.
+    Hold(f64),
+}
+
+/// The tempo throughout a score, which converts positions in the score to seconds and back.
+///
+/// Positions are lengths from the start of the score, in whole notes.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::duration::Fraction;
+/// use note_pen::time::{Tempo, TempoChange, TempoMap};
+/// let mut tempo = TempoMap::new(Tempo::new(60.0));
+/// tempo.add(Fraction::ONE, TempoChange::Set(Tempo::new(120.0)));
+/// // A measure of 4/4 at 60 bpm, then half a measure at 120 bpm.
+/// assert_eq!(tempo.seconds_at(Fraction::new(3, 2)), 5.0);
+/// assert_eq!(tempo.position_at(5.0), Fraction::new(3, 2));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TempoMap {
+    pub initial: Tempo,
+    changes: Vec<(Fraction, TempoChange)>,
+}
+
+/// A stretch of a [`TempoMap`] where the tempo changes linearly, or not at all.
+struct TempoSegment {
+    start: Fraction,
+    /// The end of the segment, or `None` if it lasts until the end of the score.
+    end: Option<Fraction>,
+    /// The seconds at the start of the segment.
+    seconds: f64,
+    /// The rate at the start and end of the segment, in whole notes per second.
+    from: f64,
+    to: f64,
+    tempo: Tempo,
+}
+
+impl TempoSegment {
+    /// The change in rate per whole note.
+    fn slope(&self) -> f64 {
+        match self.end {
+            Some(end) if (self.to - self.from).abs() > f64::EPSILON => {
+                (self.to - self.from) / (end - self.start).to_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn rate_at(&self, offset: f64) -> f64 {
+        self.from + self.slope() * offset
+    }
+
+    /// The seconds taken to travel `offset` whole notes into the segment.
+    fn seconds_at(&self, offset: f64) -> f64 {
+        let slope = self.slope();
+        if slope == 0.0 {
+            offset / self.from
+        } else {
+            (self.rate_at(offset) / self.from).ln() / slope
+        }
+    }
+
+    /// The whole notes travelled `seconds` into the segment.
+    fn offset_at(&self, seconds: f64) -> f64 {
+        let slope = self.slope();
+        if slope == 0.0 {
+            seconds * self.from
+        } else {
+            self.from * ((seconds * slope).exp() - 1.0) / slope
+        }
+    }
+}
+
+/// The resolution positions computed from seconds are rounded to, in ticks per quarter note.
+const POSITION_TICKS: u64 = 960;
+
+impl TempoMap {
+    /// Creates a tempo map with a single tempo.
+    pub const fn new(initial: Tempo) -> Self {
+        Self {
+            initial,
+            changes: vec![],
+        }
+    }
+
+    /// Adds a change at `position`, after any other changes at the same position.
+    pub fn add(&mut self, position: Fraction, change: TempoChange) {
+        let index = self.changes.partition_point(|(at, _)| *at <= position);
+        self.changes.insert(index, (position, change));
+    }
+
+    /// The changes, in order.
+    pub fn changes(&self) -> &[(Fraction, TempoChange)] {
+        &self.changes
+    }
+
+    /// Splits the map into stretches of steady or linearly changing tempo.
+    fn segments(&self) -> Vec<TempoSegment> {
+        let mut segments = vec![];
+        let mut position = Fraction::ZERO;
+        let mut seconds = 0.0;
+        let mut tempo = self.initial;
+        let mut rate = tempo.whole_notes_per_second();
+        // The ramp in progress, as its start, end and rates.
+        let mut ramp: Option<(Fraction, Fraction, f64, f64)> = None;
+        let mut changes = self.changes.iter().peekable();
+        loop {
+            let next_change = changes.peek().map(|(at, _)| *at);
+            let end = match (next_change, ramp) {
+                (Some(at), Some((_, ramp_end, _, _))) => Some(at.min(ramp_end)),
+                (Some(at), None) => Some(at),
+                (None, Some((_, ramp_end, _, _))) => Some(ramp_end),
+                (None, None) => None,
+            };
+            let Some(end) = end else {
+                segments.push(TempoSegment {
+                    start: position,
+                    end: None,
+                    seconds,
+                    from: rate,
+                    to: rate,
+                    tempo,
+                });
+                return segments;
+            };
+            if end > position {
+                let to = match ramp {
+                    Some((start, ramp_end, from, to)) => {
+                        let progress = ((end - start).to_f64()) / (ramp_end - start).to_f64();
+                        from + (to - from) * progress
+                    }
+                    None => rate,
+                };
+                let segment = TempoSegment {
+                    start: position,
+                    end: Some(end),
+                    seconds,
+                    from: rate,
+                    to,
+                    tempo,
+                };
+                seconds += segment.seconds_at((end - position).to_f64());
+                segments.push(segment);
+                rate = to;
+                position = end;
+            }
+            if ramp.is_some_and(|(_, ramp_end, _, _)| ramp_end == position) {
+                ramp = None;
+            }
+            while let Some((_, change)) = changes.next_if(|(at, _)| *at == position) {
+                match change {
+                    TempoChange::Set(new) => {
+                        tempo = *new;
+                        rate = tempo.whole_notes_per_second();
+                        ramp = None;
+                    }
+                    TempoChange::Ramp { length, tempo: new } => {
+                        tempo = *new;
+                        if length.is_zero() {
+                            rate = tempo.whole_notes_per_second();
+                            ramp = None;
+                        } else {
+                            let target = tempo.whole_notes_per_second();
+                            ramp = Some((position, position + *length, rate, target));
+                        }
+                    }
+                    TempoChange::Hold(hold) => seconds += hold.max(0.0),
+                }
+            }
+        }
+    }
+
+    /// The segment containing `position`.
+    fn segment_at(segments: &[TempoSegment], position: Fraction) -> &TempoSegment {
+        let index = segments.partition_point(|segment| segment.start <= position);
+        &segments[index.saturating_sub(1)]
+    }
+
+    /// The tempo at `position`, part way through any accelerando or ritardando.
+    /// During a ramp, the tempo is counted in the beat of the tempo being approached.
+    pub fn tempo_at(&self, position: Fraction) -> Tempo {
+        let segments = self.segments();
+        let segment = Self::segment_at(&segments, position);
+        let rate = segment.rate_at((position - segment.start).to_f64());
+        segment.tempo.with_rate(rate)
+    }
+
+    /// The seconds from the start of the score to `position`, including any holds before it.
+    pub fn seconds_at(&self, position: Fraction) -> f64 {
+        let segments = self.segments();
+        let segment = Self::segment_at(&segments, position);
+        segment.seconds + segment.seconds_at((position - segment.start).to_f64())
+    }
+
+    /// The seconds from the start of the score to a position given in whole notes as a floating point number,
+    /// such as a playback onset that has been moved off the notated grid.
+    pub fn seconds_at_offset(&self, position: f64) -> f64 {
+        let segments = self.segments();
+        let index = segments.partition_point(|segment| segment.start.to_f64() <= position);
+        let segment = &segments[index.saturating_sub(1)];
+        let offset = (position - segment.start.to_f64()).max(0.0);
+        segment.seconds + segment.seconds_at(offset)
+    }
+
+    /// The position reached after `seconds`, rounded to a 3840th of a whole note.
+    /// During a hold, the position stays where the hold is.
+    pub fn position_at(&self, seconds: f64) -> Fraction {
+        let segments = self.segments();
+        let seconds = seconds.max(0.0);
+        let index = segments.partition_point(|segment| segment.seconds <= seconds);
+        let segment = &segments[index.saturating_sub(1)];
+        let mut offset = segment.offset_at(seconds - segment.seconds);
+        if let Some(end) = segment.end {
+            offset = offset.min((end - segment.start).to_f64());
+        }
+        let ticks = (offset * 4.0 * POSITION_TICKS as f64).round() as u64;
+        segment.start + Fraction::from_ticks(ticks, POSITION_TICKS)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct Measure(pub usize);
@@ -38,7 +308,7 @@ impl Measure {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct Beat(pub usize);
@@ -71,11 +341,17 @@ impl BeatFraction {
         }
     }
 
+    /// The length of the fraction of a beat.
+    #[inline]
+    pub fn length(&self) -> Fraction {
+        self.denominator.length() * self.numerator as u64
+    }
+
     #[inline]
     pub fn simplify(&self) -> Self {
         let mut numerator = self.numerator;
         let mut denominator = self.denominator;
-        while numerator % 2 == 0 && denominator != PrimitiveDuration::WHOLE {
+        while numerator.is_multiple_of(2) && denominator != PrimitiveDuration::LONGEST {
             numerator /= 2;
             denominator = denominator.double();
         }
@@ -85,3 +361,243 @@ impl BeatFraction {
         }
     }
 }
+
+/// A position in a score, as a measure and the length from the start of that measure.
+///
+/// Positions are ordered by measure, then by offset,
+/// so offsets should be kept within their measure with [`ScorePosition::normalize`].
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::duration::Fraction;
+/// use note_pen::time::{Measure, ScorePosition};
+/// let time_signatures = [TimeSignature::new(3, 4), TimeSignature::new(4, 4)];
+/// let position = ScorePosition::new(Measure::new(0), Fraction::new(1, 2)) + Fraction::new(3, 8);
+/// let position = position.normalize(&time_signatures).unwrap();
+/// assert_eq!(position, ScorePosition::new(Measure::new(1), Fraction::new(1, 8)));
+/// assert_eq!(position.to_string(), "m2 b1.5");
+/// assert_eq!(position.absolute(&time_signatures), Some(Fraction::new(7, 8)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScorePosition {
+    pub measure: Measure,
+    pub offset: Fraction,
+}
+
+impl ScorePosition {
+    /// The start of the score.
+    pub const START: Self = Self::new(Measure::new(0), Fraction::ZERO);
+
+    #[inline]
+    pub const fn new(measure: Measure, offset: Fraction) -> Self {
+        Self { measure, offset }
+    }
+
+    /// Creates the position of a beat, and a fraction of the next beat, in a measure.
+    pub fn from_beat(
+        measure: Measure,
+        beat: Beat,
+        fraction: BeatFraction,
+        time_signature: &TimeSignature,
+    ) -> Self {
+        let offsets = time_signature.beat_offsets();
+        let offset = if offsets.is_empty() {
+            Fraction::ZERO
+        } else {
+            // Beats past the end of the measure continue into the following measures.
+            let measures = (beat.value() / offsets.len()) as u64;
+            time_signature.measure_length() * measures + offsets[beat.value() % offsets.len()]
+        };
+        let offset = offset + fraction.length();
+        Self::new(measure, offset)
+    }
+
+    /// Finds the position `offset` from the start of the score,
+    /// given the time signature of each measure.
+    /// The end of the score is the end of the last measure.
+    /// Returns `None` if the offset is past the end of the last measure.
+    pub fn from_absolute(offset: Fraction, time_signatures: &[TimeSignature]) -> Option<Self> {
+        Self::new(Measure::new(0), offset).normalize(time_signatures)
+    }
+
+    /// The length from the start of the score to the position,
+    /// given the time signature of each measure.
+    /// Returns `None` if the measure is not in the score or the offset is past the end of its measure,
+    /// so the position should be normalized first with [`ScorePosition::normalize`].
+    /// Measures in free time may have any offset.
+    pub fn absolute(&self, time_signatures: &[TimeSignature]) -> Option<Fraction> {
+        let length = time_signatures.get(self.measure.value())?.measure_length();
+        if !length.is_zero() && self.offset > length {
+            return None;
+        }
+        let start = time_signatures[..self.measure.value()]
+            .iter()
+            .map(TimeSignature::measure_length)
+            .sum::<Fraction>();
+        Some(start + self.offset)
+    }
+
+    /// Moves an offset that runs past the end of its measure into the measure it falls in,
+    /// given the time signature of each measure.
+    /// The end of the last measure is kept in the last measure, as the end of the score.
+    /// Returns `None` if the position is past the end of the last measure.
+    pub fn normalize(&self, time_signatures: &[TimeSignature]) -> Option<Self> {
+        let mut measure = self.measure.value();
+        let mut offset = self.offset;
+        loop {
+            let length = time_signatures.get(measure)?.measure_length();
+            // Free time has no end, and the end of the last measure is the end of the score.
+            let stays =
+                length.is_zero() || (measure + 1 == time_signatures.len() && offset == length);
+            match offset.checked_sub(length) {
+                Some(rest) if !stays => {
+                    offset = rest;
+                    measure += 1;
+                }
+                _ => return Some(Self::new(Measure::new(measure), offset)),
+            }
+        }
+    }
+
+    /// The offset in beats of the time signature, counting from 0,
+    /// with any part of a beat as a fraction of that beat.
+    pub fn beats(&self, time_signature: &TimeSignature) -> Fraction {
+        let mut beats = Fraction::ZERO;
+        let mut offset = self.offset;
+        let lengths = time_signature.beat_lengths();
+        let Some(&last) = lengths.last() else {
+            return beats;
+        };
+        // Offsets past the end of the measure are counted in the last beat's length.
+        for length in lengths.into_iter().chain(std::iter::repeat(last)) {
+            match offset.checked_sub(length) {
+                Some(rest) => {
+                    beats = beats + Fraction::ONE;
+                    offset = rest;
+                }
+                None => {
+                    return beats + offset * Fraction::new(length.denominator(), length.numerator())
+                }
+            }
+        }
+        unreachable!("the beats repeat forever")
+    }
+}
+
+impl Add<Fraction> for ScorePosition {
+    type Output = Self;
+
+    /// Moves the position later, within the same measure.
+    fn add(self, rhs: Fraction) -> Self::Output {
+        Self::new(self.measure, self.offset + rhs)
+    }
+}
+
+impl Display for ScorePosition {
+    /// Writes the measure number and the quarter note beat, both counting from 1, like `m12 b3.5`.
+    /// Beats that are not a terminating decimal are written as a fraction, like `b1+1/3`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let beats = self.offset * 4;
+        let whole = beats.numerator() / beats.denominator();
+        let remainder = Fraction::new(beats.numerator() % beats.denominator(), beats.denominator());
+        write!(f, "m{} b{}", self.measure.value() + 1, whole + 1)?;
+        if remainder.is_zero() {
+            return Ok(());
+        }
+        let mut denominator = remainder.denominator();
+        while denominator.is_multiple_of(2) {
+            denominator /= 2;
+        }
+        while denominator.is_multiple_of(5) {
+            denominator /= 5;
+        }
+        if denominator != 1 {
+            return write!(f, "+{remainder}");
+        }
+        let mut numerator = remainder.numerator();
+        write!(f, ".")?;
+        while numerator != 0 {
+            numerator *= 10;
+            write!(f, "{}", numerator / remainder.denominator())?;
+            numerator %= remainder.denominator();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tempo_map() {
+        let mut tempo = TempoMap::new(Tempo::new(60.0));
+        tempo.add(
+            Fraction::ONE,
+            TempoChange::Ramp {
+                length: Fraction::ONE,
+                tempo: Tempo::new(120.0),
+            },
+        );
+        tempo.add(Fraction::new(2, 1), TempoChange::Hold(3.0));
+        // A linear ramp from 1 to 2 beats per second over 4 beats takes 4 ln 2 seconds.
+        let ramp_end = 4.0 + 4.0 * 2f64.ln();
+        assert!((tempo.seconds_at(Fraction::new(2, 1)) - (ramp_end + 3.0)).abs() < 1e-9);
+        assert!((tempo.tempo_at(Fraction::new(3, 2)).bpm - 90.0).abs() < 1e-9);
+        assert_eq!(tempo.position_at(ramp_end + 1.0), Fraction::new(2, 1));
+        assert_eq!(tempo.position_at(ramp_end + 3.5), Fraction::new(9, 4));
+        let middle = tempo.seconds_at(Fraction::new(3, 2));
+        assert_eq!(tempo.position_at(middle), Fraction::new(3, 2));
+    }
+
+    #[test]
+    fn test_score_position() {
+        let time_signatures = [TimeSignature::new(6, 8), TimeSignature::new(6, 8)];
+        let position = ScorePosition::from_beat(
+            Measure::new(1),
+            Beat::new(1),
+            BeatFraction::new(1, PrimitiveDuration::EIGHTH),
+            &time_signatures[1],
+        );
+        assert_eq!(position.offset, Fraction::new(1, 2));
+        assert_eq!(position.beats(&time_signatures[1]), Fraction::new(4, 3));
+        assert_eq!(position.to_string(), "m2 b3");
+        assert_eq!(
+            ScorePosition::from_absolute(Fraction::new(5, 4), &time_signatures),
+            Some(position)
+        );
+        assert!(ScorePosition::START < position);
+        // The end of the score is the end of the last measure.
+        let end = ScorePosition::new(Measure::new(1), Fraction::new(3, 4));
+        assert_eq!(
+            ScorePosition::from_absolute(Fraction::new(3, 2), &time_signatures),
+            Some(end)
+        );
+        assert_eq!(end.absolute(&time_signatures), Some(Fraction::new(3, 2)));
+        assert_eq!(
+            ScorePosition::from_absolute(Fraction::new(13, 8), &time_signatures),
+            None
+        );
+        // The end of a measure before the last is the start of the next one.
+        assert_eq!(
+            ScorePosition::from_absolute(Fraction::new(3, 4), &time_signatures),
+            Some(ScorePosition::new(Measure::new(1), Fraction::ZERO))
+        );
+        // Positions outside the score or past the end of their measure have no absolute offset.
+        let past = ScorePosition::new(Measure::new(2), Fraction::ZERO);
+        assert_eq!(past.absolute(&time_signatures), None);
+        let overflowing = ScorePosition::new(Measure::new(0), Fraction::new(7, 8));
+        assert_eq!(overflowing.absolute(&time_signatures), None);
+        assert_eq!(
+            overflowing
+                .normalize(&time_signatures)
+                .unwrap()
+                .absolute(&time_signatures),
+            Some(Fraction::new(7, 8))
+        );
+        let triplet = ScorePosition::new(Measure::new(0), Fraction::new(1, 12));
+        assert_eq!(triplet.to_string(), "m1 b1+1/3");
+    }
+}
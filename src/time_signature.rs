@@ -1,22 +1,86 @@
-use crate::duration::PrimitiveDuration;
+use crate::duration::{Duration, Fraction, PrimitiveDuration};
 
+/// How a time signature is written, when it is not written as its numbers.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TimeSignatureSymbol {
     CommonTime,
     CutTime,
     Custom(String),
+    /// The beat groups are written as the numerator, like (2+2+3)/8.
+    Additive,
+    /// Several signatures are written added together, like 3/4 + 6/8.
+    Composite(Vec<TimeSignature>),
+    /// The numerator is a fraction, like 2½/4.
+    Fractional {
+        numerator: Fraction,
+        beat_value: u64,
+    },
+    /// Free time, which is not divided into regular measures.
+    SenzaMisura,
+}
+
+/// How strongly a position in a measure is felt, from the downbeat to subdivisions of the beat.
+///
+/// Weights are ordered from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetricWeight {
+    /// The first beat of the measure.
+    Downbeat,
+    /// The middle of a measure of four or more beats.
+    StrongBeat,
+    /// Any other beat.
+    Beat,
+    /// A position between beats, where 1 divides the beat once, 2 divides that again, and so on.
+    Offbeat(u8),
+}
+
+impl MetricWeight {
+    /// The number of levels below the measure, where the downbeat is 0.
+    const fn depth(&self) -> usize {
+        match self {
+            Self::Downbeat => 0,
+            Self::StrongBeat => 1,
+            Self::Beat => 2,
+            Self::Offbeat(level) => 2 + *level as usize,
+        }
+    }
+
+    /// Check if the position is the downbeat or another strong beat.
+    #[inline]
+    pub const fn is_strong(&self) -> bool {
+        matches!(self, Self::Downbeat | Self::StrongBeat)
+    }
+}
+
+impl PartialOrd for MetricWeight {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MetricWeight {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.depth().cmp(&self.depth())
+    }
 }
 
 /// Represents a time signature.
 ///
-/// Internally, it stores the number of notes in a measure and the beat value.
+/// Internally, it stores the number of notes in a measure and the beat value,
+/// along with how the notes are grouped into beats.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeSignature {
     pub notes: u64,
     pub beat_value: u64,
+    /// The number of notes in each beat, like `[2, 2, 3]` for (2+2+3)/8.
+    /// When empty, the notes are grouped in threes in compound time and are each a beat otherwise.
+    pub groups: Vec<u64>,
     pub symbol: Option<TimeSignatureSymbol>,
+    /// Another signature the music may equally be counted in, written in parentheses, like 3/4 (6/8).
+    pub interchangeable: Option<Box<TimeSignature>>,
 }
 
 impl TimeSignature {
@@ -24,13 +88,25 @@ impl TimeSignature {
     pub const COMMON_TIME: Self = Self {
         notes: 4,
         beat_value: 4,
+        groups: vec![],
         symbol: Some(TimeSignatureSymbol::CommonTime),
+        interchangeable: None,
     };
     /// 2/2 time signature
     pub const CUT_TIME: Self = Self {
         notes: 2,
         beat_value: 2,
+        groups: vec![],
         symbol: Some(TimeSignatureSymbol::CutTime),
+        interchangeable: None,
+    };
+    /// Free time, whose measures may be any length.
+    pub const FREE: Self = Self {
+        notes: 0,
+        beat_value: 4,
+        groups: vec![],
+        symbol: Some(TimeSignatureSymbol::SenzaMisura),
+        interchangeable: None,
     };
 
     /// Create a new time signature.
@@ -48,14 +124,27 @@ impl TimeSignature {
         Self {
             notes,
             beat_value,
+            groups: vec![],
             symbol: None,
+            interchangeable: None,
         }
     }
 
     /// Create a simple time signature.
+    ///
+    /// Note values longer than a whole note cannot be written as a denominator,
+    /// so they are counted in whole notes: 3 breves are 6/1.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// assert_eq!(TimeSignature::simple(3, PrimitiveDuration::QUARTER), TimeSignature::new(3, 4));
+    /// assert_eq!(TimeSignature::simple(3, PrimitiveDuration::BREVE), TimeSignature::new(6, 1));
+    /// ```
     #[inline]
     pub const fn simple(beats: u64, value: PrimitiveDuration) -> Self {
-        Self::new(beats, value.value())
+        let length = value.length();
+        Self::new(beats * length.numerator(), length.denominator())
     }
 
     /// Create a compound time signature.
@@ -70,22 +159,154 @@ impl TimeSignature {
     /// ```
     #[inline]
     pub const fn compound(beats: u64, value: PrimitiveDuration) -> Self {
-        Self::new(beats * 3, value.value())
+        let length = value.length();
+        Self::new(beats * 3 * length.numerator(), length.denominator())
     }
 
-    /// Check if the time signature is compound.
+    /// Create a time signature with irregular beats, written with the groups as the numerator.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// // (2+2+3)/8 time signature
+    /// let time_signature = TimeSignature::additive(&[2, 2, 3], 8);
+    /// assert_eq!(time_signature.beats(), 3);
+    /// assert_eq!(time_signature.beat_lengths()[2], Fraction::new(3, 8));
+    /// assert!(!time_signature.is_simple() && !time_signature.is_compound());
+    /// ```
+    pub fn additive(groups: &[u64], beat_value: u64) -> Self {
+        Self {
+            groups: groups.to_vec(),
+            symbol: Some(TimeSignatureSymbol::Additive),
+            ..Self::new(groups.iter().sum(), beat_value)
+        }
+    }
+
+    /// Create a time signature that alternates between several signatures in each measure,
+    /// like 3/4 + 6/8, keeping the beats of each.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// let time_signature =
+    ///     TimeSignature::composite(&[TimeSignature::new(3, 4), TimeSignature::new(6, 8)]);
+    /// assert_eq!(time_signature, TimeSignature::additive(&[2, 2, 2, 3, 3], 8));
+    /// ```
+    pub fn composite(parts: &[TimeSignature]) -> Self {
+        let beat_value = parts
+            .iter()
+            .fold(1, |beat_value, part| lcm(beat_value, part.beat_value));
+        let groups = parts
+            .iter()
+            .flat_map(|part| {
+                let scale = beat_value / part.beat_value;
+                part.groups().into_iter().map(move |group| group * scale)
+            })
+            .collect::<Vec<_>>();
+        Self {
+            groups: groups.clone(),
+            symbol: Some(TimeSignatureSymbol::Composite(parts.to_vec())),
+            ..Self::new(groups.iter().sum(), beat_value)
+        }
+    }
+
+    /// Create a time signature with a fractional numerator, like 2½/4,
+    /// where the fraction of a beat is a beat of its own at the end of the measure.
+    /// Returns `None` if the numerator's denominator is not a power of two.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let time_signature = TimeSignature::fractional(Fraction::new(5, 2), 4).unwrap();
+    /// assert_eq!(time_signature.measure_length(), Fraction::new(5, 8));
+    /// assert_eq!(time_signature.beats(), 3);
+    /// ```
+    pub fn fractional(numerator: Fraction, beat_value: u64) -> Option<Self> {
+        let parts = numerator.denominator();
+        if !parts.is_power_of_two() {
+            return None;
+        }
+        let notes = numerator.numerator();
+        let mut groups = vec![parts; (notes / parts) as usize];
+        if !notes.is_multiple_of(parts) {
+            groups.push(notes % parts);
+        }
+        Some(Self {
+            groups,
+            symbol: Some(TimeSignatureSymbol::Fractional {
+                numerator,
+                beat_value,
+            }),
+            ..Self::new(notes, beat_value * parts)
+        })
+    }
+
+    /// Groups the notes into beats of the given numbers of notes, without changing how the signature is written.
+    ///
+    /// # Panics
+    /// Panics if the groups do not add up to the number of notes in the measure.
+    pub fn with_groups(self, groups: &[u64]) -> Self {
+        assert_eq!(
+            groups.iter().sum::<u64>(),
+            self.notes,
+            "the groups must fill the measure"
+        );
+        Self {
+            groups: groups.to_vec(),
+            ..self
+        }
+    }
+
+    /// Adds a signature the music may equally be counted in.
+    pub fn with_interchangeable(self, interchangeable: TimeSignature) -> Self {
+        Self {
+            interchangeable: Some(Box::new(interchangeable)),
+            ..self
+        }
+    }
+
+    /// Check if the time signature is free time, without regular measures.
+    #[inline]
+    pub const fn is_free(&self) -> bool {
+        self.notes == 0
+    }
+
+    /// The number of notes in each beat, from [`TimeSignature::groups`]
+    /// or the usual grouping when none is given.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// assert_eq!(TimeSignature::new(6, 8).groups(), vec![3, 3]);
+    /// assert_eq!(TimeSignature::new(3, 4).groups(), vec![1, 1, 1]);
+    /// ```
+    pub fn groups(&self) -> Vec<u64> {
+        if !self.groups.is_empty() {
+            self.groups.clone()
+        } else if self.notes > 3 && self.notes.is_multiple_of(3) {
+            vec![3; self.notes as usize / 3]
+        } else {
+            vec![1; self.notes as usize]
+        }
+    }
+
+    /// Check if the time signature is compound, with every beat divided in three.
     /// # Examples
     /// ```rust
     /// use note_pen::prelude::*;
     /// let time_signature = TimeSignature::new(6, 8);
     /// assert!(time_signature.is_compound());
+    /// let time_signature = TimeSignature::new(3, 4);
+    /// assert!(!time_signature.is_compound());
     /// ```
-    #[inline]
-    pub const fn is_compound(&self) -> bool {
-        self.notes % 3 == 0
+    pub fn is_compound(&self) -> bool {
+        let groups = self.groups();
+        !groups.is_empty() && groups.iter().all(|&group| group == 3)
     }
 
-    /// Check if the time signature is simple.
+    /// Check if the time signature is simple, with every beat a single note of the beat value.
     /// # Examples
     /// ```rust
     /// use note_pen::prelude::*;
@@ -93,12 +314,13 @@ impl TimeSignature {
     /// assert!(time_signature.is_simple());
     /// let time_signature = TimeSignature::new(5, 4);
     /// assert!(time_signature.is_simple());
-    #[inline]
-    pub const fn is_simple(&self) -> bool {
-        !self.is_compound()
+    /// ```
+    pub fn is_simple(&self) -> bool {
+        let groups = self.groups();
+        !groups.is_empty() && groups.iter().all(|&group| group == 1)
     }
 
-    /// Get the number of beats in a measure.
+    /// Get the number of beats in a measure, which is the number of groups.
     /// For compound time signatures, it returns the number of dotted notes
     /// -- the number of notes in a measure divided by 3.
     /// # Examples
@@ -110,14 +332,246 @@ impl TimeSignature {
     /// assert_eq!(time_signature.beats(), 4);
     /// ```
     #[inline]
-    pub const fn beats(&self) -> u64 {
-        if self.is_compound() {
-            self.notes / 3
+    pub fn beats(&self) -> u64 {
+        self.groups().len() as u64
+    }
+
+    /// The length of a measure, which is 0 in free time.
+    #[inline]
+    pub const fn measure_length(&self) -> Fraction {
+        Fraction::new(self.notes, self.beat_value)
+    }
+
+    /// The length of each beat.
+    pub fn beat_lengths(&self) -> Vec<Fraction> {
+        self.groups()
+            .into_iter()
+            .map(|group| Fraction::new(group, self.beat_value))
+            .collect()
+    }
+
+    /// The offset of each beat from the start of the measure.
+    pub fn beat_offsets(&self) -> Vec<Fraction> {
+        self.beat_lengths()
+            .into_iter()
+            .scan(Fraction::ZERO, |offset, length| {
+                let start = *offset;
+                *offset = *offset + length;
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// The length of a beat, which is dotted in compound time.
+    /// When beats have different lengths, this is the length of the first beat.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// assert_eq!(TimeSignature::new(6, 8).beat_length(), Fraction::new(3, 8));
+    /// assert_eq!(TimeSignature::new(3, 4).beat_length(), Fraction::new(1, 4));
+    /// ```
+    #[inline]
+    pub fn beat_length(&self) -> Fraction {
+        let group = self.groups().first().copied().unwrap_or(1);
+        Fraction::new(group, self.beat_value)
+    }
+
+    /// Splits a length starting `start` into a measure into notatable durations to be tied together.
+    ///
+    /// Notes are split at barlines, and are otherwise only written as one duration
+    /// when they do not obscure the beat: a note starting on a beat or half bar may last as long
+    /// as the unit it starts, while a note starting elsewhere is split where it crosses a beat,
+    /// unless it ends exactly at the end of that beat.
+    /// So in 4/4 a half note on beat 2 is written as two tied quarters, and in 6/8 the beats are dotted quarters.
+    /// Returns `None` if the length cannot be written without tuplets.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// let durations = TimeSignature::new(4, 4)
+    ///     .split(Fraction::new(1, 4), Fraction::new(1, 2))
+    ///     .unwrap();
+    /// assert_eq!(durations, vec![Duration::QUARTER, Duration::QUARTER]);
+    /// let durations = TimeSignature::new(6, 8)
+    ///     .split(Fraction::ZERO, Fraction::new(3, 4))
+    ///     .unwrap();
+    /// assert_eq!(durations, vec![Duration::new(PrimitiveDuration::HALF, 1)]);
+    /// ```
+    pub fn split(&self, start: Fraction, length: Fraction) -> Option<Vec<Duration>> {
+        let measure = self.measure_length();
+        if measure.is_zero() {
+            return None;
+        }
+        // Start counting from the beginning of the measure that `start` falls in.
+        let mut position = start;
+        while position >= measure {
+            position = position - measure;
+        }
+        let mut remaining = length;
+        let mut durations = vec![];
+        while !remaining.is_zero() {
+            let until_barline = measure - position;
+            let part = remaining.min(until_barline);
+            self.split_node(
+                Fraction::ZERO,
+                measure,
+                0,
+                position,
+                position + part,
+                &mut durations,
+            )?;
+            remaining = remaining - part;
+            position = Fraction::ZERO;
+        }
+        Some(durations)
+    }
+
+    /// The lengths of the metric units that a unit at `depth` divides into,
+    /// where depth 0 is the measure.
+    fn divisions(&self, length: Fraction, depth: usize) -> Vec<Fraction> {
+        let beats = self.beats() as usize;
+        let split = |parts: u64| vec![length * Fraction::new(1, parts); parts as usize];
+        let beat_depth = self.beat_depth();
+        if depth == 0 && beat_depth == 2 {
+            split(2)
+        } else if depth == 0 {
+            self.beat_lengths()
+        } else if depth < beat_depth {
+            vec![self.beat_length(); beats / 2]
+        } else if depth == beat_depth {
+            let notes = (length * self.beat_value).numerator();
+            match notes {
+                3 => split(3),
+                notes if notes > 3 && notes % 2 == 1 => split(notes),
+                _ => split(2),
+            }
         } else {
-            self.notes
+            split(2)
+        }
+    }
+
+    /// The depth of the beats in the metric hierarchy below the measure.
+    /// Measures of four or more equal beats divide into half bars first.
+    fn beat_depth(&self) -> usize {
+        let groups = self.groups();
+        let beats = groups.len();
+        if beats >= 4 && beats.is_multiple_of(2) && groups.iter().all(|&group| group == groups[0]) {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// The metrical weight of a position in the measure,
+    /// following the hierarchy of measure, half bar, beat and ever smaller divisions of the beat.
+    /// Compound beats divide in three, irregular beats into their notes, and everything else in two.
+    /// Positions past the end of the measure are taken in the following measures,
+    /// and positions off the grid, like those inside tuplets,
+    /// are one level weaker than the shortest division.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use note_pen::prelude::*;
+    /// use note_pen::duration::Fraction;
+    /// use note_pen::MetricWeight;
+    /// let common = TimeSignature::COMMON_TIME;
+    /// assert_eq!(common.metric_weight(Fraction::ZERO), MetricWeight::Downbeat);
+    /// assert_eq!(common.metric_weight(Fraction::new(1, 2)), MetricWeight::StrongBeat);
+    /// assert_eq!(common.metric_weight(Fraction::new(3, 4)), MetricWeight::Beat);
+    /// assert_eq!(common.metric_weight(Fraction::new(7, 8)), MetricWeight::Offbeat(1));
+    /// let compound = TimeSignature::new(6, 8);
+    /// assert_eq!(compound.metric_weight(Fraction::new(1, 4)), MetricWeight::Offbeat(1));
+    /// assert!(compound.metric_weight(Fraction::new(3, 8)) > compound.metric_weight(Fraction::new(1, 8)));
+    /// ```
+    pub fn metric_weight(&self, offset: Fraction) -> MetricWeight {
+        let measure = self.measure_length();
+        let mut offset = offset;
+        if !measure.is_zero() {
+            while offset >= measure {
+                offset = offset - measure;
+            }
+        }
+        let beat_depth = self.beat_depth();
+        let depth = self.metric_depth(Fraction::ZERO, measure, 0, offset);
+        match depth {
+            0 => MetricWeight::Downbeat,
+            depth if depth < beat_depth => MetricWeight::StrongBeat,
+            depth if depth == beat_depth => MetricWeight::Beat,
+            depth => MetricWeight::Offbeat((depth - beat_depth).min(u8::MAX as usize) as u8),
         }
     }
 
+    /// The depth of the first unit of the hierarchy that starts at `offset`.
+    fn metric_depth(
+        &self,
+        node_start: Fraction,
+        node_length: Fraction,
+        depth: usize,
+        offset: Fraction,
+    ) -> usize {
+        if offset == node_start || node_length < Fraction::new(1, 256) {
+            return depth;
+        }
+        let mut child_start = node_start;
+        for child in self.divisions(node_length, depth) {
+            if offset < child_start + child {
+                return self.metric_depth(child_start, child, depth + 1, offset);
+            }
+            child_start = child_start + child;
+        }
+        depth + 1
+    }
+
+    fn split_node(
+        &self,
+        node_start: Fraction,
+        node_length: Fraction,
+        depth: usize,
+        start: Fraction,
+        end: Fraction,
+        durations: &mut Vec<Duration>,
+    ) -> Option<()> {
+        let node_end = node_start + node_length;
+        let notatable = Duration::try_from(end - start)
+            .ok()
+            .filter(|duration| duration.dots <= 2);
+        if let Some(duration) = notatable {
+            if start == node_start {
+                durations.push(duration);
+                return Some(());
+            }
+        }
+        // Past the shortest notatable unit, the length needs a tuplet.
+        if node_length < Fraction::new(1, 256) {
+            return None;
+        }
+        let mut child_start = node_start;
+        let children = self.divisions(node_length, depth);
+        if let Some(duration) = notatable {
+            let on_boundary = children.iter().scan(node_start, |position, &child| {
+                let boundary = *position;
+                *position = *position + child;
+                Some(boundary)
+            });
+            if end == node_end && on_boundary.into_iter().any(|boundary| boundary == start) {
+                durations.push(duration);
+                return Some(());
+            }
+        }
+        for child in children {
+            let child_end = child_start + child;
+            let (from, to) = (start.max(child_start), end.min(child_end));
+            if from < to {
+                self.split_node(child_start, child, depth + 1, from, to, durations)?;
+            }
+            child_start = child_end;
+        }
+        Some(())
+    }
+
     /// Get the beat value.
     /// # Examples
     /// ```rust
@@ -126,10 +580,12 @@ impl TimeSignature {
     /// assert_eq!(time_signature.value(), PrimitiveDuration::EIGHTH);
     /// let time_signature = TimeSignature::new(4, 4);
     /// assert_eq!(time_signature.value(), PrimitiveDuration::QUARTER);
+    /// let time_signature = TimeSignature::new(3, 128);
+    /// assert_eq!(time_signature.value(), PrimitiveDuration::HUNDRED_TWENTY_EIGHTH);
     /// ```
     /// # Panics
     /// It panics
-    /// if the beat value is invalid and [`PrimitiveDuration::try_from`] returns an error.
+    /// if the beat value is not a power of two up to 256 and [`PrimitiveDuration::try_from`] returns an error.
     #[inline]
     pub fn value(&self) -> PrimitiveDuration {
         PrimitiveDuration::try_from(self.beat_value).expect("Invalid time signature value")
@@ -138,49 +594,167 @@ impl TimeSignature {
 
 impl PartialEq for TimeSignature {
     fn eq(&self, other: &Self) -> bool {
-        self.notes == other.notes && self.beat_value == other.beat_value
+        self.notes == other.notes
+            && self.beat_value == other.beat_value
+            && self.groups() == other.groups()
     }
 }
 
+const fn lcm(a: u64, b: u64) -> u64 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
 #[cfg(feature = "midi")]
 mod midi {
-    use crate::duration::PrimitiveDuration;
     use crate::TimeSignature;
     use midi_file::core::{Clocks, DurationName};
 
+    /// MIDI clocks in a whole note.
+    const WHOLE_CLOCKS: u64 = 96;
+
     impl TimeSignature {
         /// Convert the time signature to MIDI time signature.
         pub fn denominator_to_midi(&self) -> DurationName {
-            match self.value() {
-                PrimitiveDuration::WHOLE => DurationName::Whole,
-                PrimitiveDuration::HALF => DurationName::Half,
-                PrimitiveDuration::QUARTER => DurationName::Quarter,
-                PrimitiveDuration::EIGHTH => DurationName::Eighth,
-                PrimitiveDuration::SIXTEENTH => DurationName::Sixteenth,
-                _ => unimplemented!("Unsupported time signature: {:?}", self),
-            }
+            let exponent = self.value().length().denominator().trailing_zeros();
+            DurationName::try_from(exponent as u8)
+                .expect("MIDI names every note value up to a 256th")
         }
 
+        /// The number of MIDI clocks in a beat, rounded to a whole clock.
         pub fn midi_clicks(&self) -> Clocks {
-            if self.is_compound() {
-                match self.value() {
-                    PrimitiveDuration::HALF => Clocks::DottedWhole,
-                    PrimitiveDuration::QUARTER => Clocks::DottedHalf,
-                    PrimitiveDuration::EIGHTH => Clocks::DottedQuarter,
-                    PrimitiveDuration::SIXTEENTH => Clocks::DottedEighth,
-                    PrimitiveDuration::THIRTY_SECOND => Clocks::DottedSixteenth,
-                    _ => unimplemented!("Unsupported time signature: {:?}", self),
-                }
-            } else {
-                match self.value() {
-                    PrimitiveDuration::WHOLE => Clocks::Whole,
-                    PrimitiveDuration::HALF => Clocks::Half,
-                    PrimitiveDuration::QUARTER => Clocks::Quarter,
-                    PrimitiveDuration::EIGHTH => Clocks::Eighth,
-                    PrimitiveDuration::SIXTEENTH => Clocks::Sixteenth,
-                    _ => unimplemented!("Unsupported time signature: {:?}", self),
-                }
+            let beat = self.beat_length();
+            let clocks =
+                (beat.numerator() * WHOLE_CLOCKS + beat.denominator() / 2) / beat.denominator();
+            match clocks {
+                144 => Clocks::DottedWhole,
+                96 => Clocks::Whole,
+                72 => Clocks::DottedHalf,
+                48 => Clocks::Half,
+                36 => Clocks::DottedQuarter,
+                24 => Clocks::Quarter,
+                18 => Clocks::DottedEighth,
+                12 => Clocks::Eighth,
+                9 => Clocks::DottedSixteenth,
+                6 => Clocks::Sixteenth,
+                clocks => Clocks::Other(clocks.clamp(1, u8::MAX as u64) as u8),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(
+        time_signature: TimeSignature,
+        start: (u64, u64),
+        length: (u64, u64),
+    ) -> Vec<Duration> {
+        time_signature
+            .split(
+                Fraction::new(start.0, start.1),
+                Fraction::new(length.0, length.1),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_split() {
+        let dotted = |primitive| Duration::new(primitive, 1);
+        let common = TimeSignature::COMMON_TIME;
+        assert_eq!(split(common.clone(), (0, 1), (1, 1)), [Duration::WHOLE]);
+        assert_eq!(
+            split(common.clone(), (0, 1), (3, 4)),
+            [dotted(PrimitiveDuration::HALF)]
+        );
+        // A dotted half from beat 2 would hide beat 3.
+        assert_eq!(
+            split(common.clone(), (1, 4), (3, 4)),
+            [Duration::QUARTER, Duration::HALF]
+        );
+        // Syncopations are split at the beat.
+        assert_eq!(
+            split(common.clone(), (1, 8), (1, 4)),
+            [Duration::EIGHTH, Duration::EIGHTH]
+        );
+        // Notes are tied over the barline.
+        assert_eq!(
+            split(common, (3, 4), (1, 2)),
+            [Duration::QUARTER, Duration::QUARTER]
+        );
+        let waltz = TimeSignature::new(3, 4);
+        assert_eq!(split(waltz, (1, 4), (1, 2)), [Duration::HALF]);
+        let compound = TimeSignature::new(6, 8);
+        assert_eq!(split(compound.clone(), (1, 8), (1, 4)), [Duration::QUARTER]);
+        assert_eq!(
+            split(compound.clone(), (1, 4), (1, 4)),
+            [Duration::EIGHTH, Duration::EIGHTH]
+        );
+        assert_eq!(
+            split(compound.clone(), (0, 1), (5, 8)),
+            [dotted(PrimitiveDuration::QUARTER), Duration::QUARTER]
+        );
+        assert!(compound
+            .split(Fraction::ZERO, Fraction::new(1, 3))
+            .is_none());
+    }
+
+    #[test]
+    fn test_irregular_meters() {
+        let dotted = |primitive| Duration::new(primitive, 1);
+        let additive = TimeSignature::additive(&[2, 2, 3], 8);
+        assert_eq!(additive, TimeSignature::new(7, 8).with_groups(&[2, 2, 3]));
+        assert_ne!(additive, TimeSignature::additive(&[3, 2, 2], 8));
+        assert_eq!(
+            additive.beat_offsets(),
+            [Fraction::ZERO, Fraction::new(1, 4), Fraction::new(1, 2)]
+        );
+        assert_eq!(
+            split(additive.clone(), (1, 2), (3, 8)),
+            [dotted(PrimitiveDuration::QUARTER)]
+        );
+        assert_eq!(
+            split(additive, (3, 8), (1, 4)),
+            [Duration::EIGHTH, Duration::EIGHTH]
+        );
+        let waltz = TimeSignature::new(3, 4).with_interchangeable(TimeSignature::new(6, 8));
+        assert_eq!(waltz.beats(), 3);
+        assert_eq!(
+            waltz.interchangeable.as_ref().unwrap().measure_length(),
+            waltz.measure_length()
+        );
+        let free = TimeSignature::FREE;
+        assert!(free.is_free() && !free.is_simple() && !free.is_compound());
+        assert_eq!(free.beats(), 0);
+        assert!(free.split(Fraction::ZERO, Fraction::ONE).is_none());
+    }
+
+    #[test]
+    fn test_metric_weight() {
+        let additive = TimeSignature::additive(&[2, 2, 3], 8);
+        assert_eq!(
+            additive.metric_weight(Fraction::new(1, 2)),
+            MetricWeight::Beat
+        );
+        assert_eq!(
+            additive.metric_weight(Fraction::new(5, 8)),
+            MetricWeight::Offbeat(1)
+        );
+        assert_eq!(
+            additive.metric_weight(Fraction::new(7, 8)),
+            MetricWeight::Downbeat
+        );
+        let common = TimeSignature::COMMON_TIME;
+        assert_eq!(
+            common.metric_weight(Fraction::new(1, 16)),
+            MetricWeight::Offbeat(2)
+        );
+        // Triplets fall between the sixteenth note grid.
+        assert!(common.metric_weight(Fraction::new(1, 12)) < MetricWeight::Offbeat(6));
+    }
+}
@@ -1,8 +1,10 @@
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tonality {
     Major,
     Minor,
     Diminished,
     Augmented,
+    /// A diminished triad that takes a minor seventh instead of a diminished seventh, like viiø7 in major.
+    HalfDiminished,
 }
@@ -0,0 +1,173 @@
+//! Tonic Sol-fa, the movable-doh notation written with the initials d r m f s l t.
+
+use crate::note::Note;
+use crate::pitch::{RelativePitch, RelativeSystem};
+use crate::solfege::{
+    octaves_above, parse_octave_marks, shift_octaves, write_octave_marks, Moveable, Solfege,
+    SolfegeSyllable,
+};
+use crate::Interval;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A note in Tonic Sol-fa, relative to doh.
+///
+/// Chromatic notes are raised to de, ri, fe, se and le or lowered to ra, ma, ba, lo and ta.
+/// Octaves are written with `'` above and `,` below.
+/// The full names doh, ray, me, fah, soh, lah and te are accepted when parsing.
+///
+/// # Examples
+/// ```rust
+/// use note_pen::prelude::*;
+/// use note_pen::tonic_sol_fa::TonicSolfa;
+/// let doh = Note::new(Alphabet::F, Accidental::Natural, 4);
+/// let note = Note::new(Alphabet::E, Accidental::Flat, 5);
+/// let sol_fa = TonicSolfa::from_note(&note, &doh, std::cmp::Ordering::Less);
+/// assert_eq!(sol_fa.to_string(), "ta");
+/// assert_eq!("soh,".parse::<TonicSolfa>().unwrap().to_string(), "s,");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TonicSolfa {
+    pub syllable: SolfegeSyllable,
+    /// The number of octaves above the octave of doh.
+    pub octave: i8,
+}
+
+impl TonicSolfa {
+    pub const fn new(syllable: SolfegeSyllable, octave: i8) -> Self {
+        Self { syllable, octave }
+    }
+
+    /// Finds the name of a note when doh is `doh`, choosing chromatic names like [`Solfege::from_note`].
+    pub fn from_note(note: &Note, doh: &Note, direction: Ordering) -> Self {
+        let syllable = Solfege::from_note(note, Moveable(*doh), direction).syllable;
+        Self::new(
+            syllable,
+            octaves_above(doh, note, syllable.into_u8() as i16),
+        )
+    }
+
+    /// Spells the note when doh is `doh`.
+    pub fn to_note(&self, doh: &Note) -> Option<Note> {
+        let note = Solfege::new(self.syllable, Moveable(*doh)).note()?;
+        shift_octaves(note, self.octave)
+    }
+
+    /// The number of half steps above doh.
+    pub const fn half_steps(&self) -> i16 {
+        self.syllable.into_u8() as i16 + self.octave as i16 * 12
+    }
+
+    const fn initials(&self) -> &'static str {
+        match self.syllable {
+            SolfegeSyllable::Do => "d",
+            SolfegeSyllable::Di => "de",
+            SolfegeSyllable::Ra => "ra",
+            SolfegeSyllable::Re => "r",
+            SolfegeSyllable::Ri => "ri",
+            SolfegeSyllable::Me => "ma",
+            SolfegeSyllable::Mi => "m",
+            SolfegeSyllable::Fa => "f",
+            SolfegeSyllable::Fi => "fe",
+            SolfegeSyllable::Se => "ba",
+            SolfegeSyllable::So => "s",
+            SolfegeSyllable::Si => "se",
+            SolfegeSyllable::Le => "lo",
+            SolfegeSyllable::La => "l",
+            SolfegeSyllable::Li => "le",
+            SolfegeSyllable::Te => "ta",
+            SolfegeSyllable::Ti => "t",
+        }
+    }
+}
+
+impl RelativeSystem for TonicSolfa {
+    fn root() -> Self {
+        Self::new(SolfegeSyllable::Do, 0)
+    }
+
+    fn base(i: RelativePitch) -> RelativePitch {
+        i
+    }
+
+    fn interval(first: Self, second: Self) -> Interval {
+        Interval::new(second.half_steps() - first.half_steps())
+    }
+}
+
+impl Display for TonicSolfa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.initials())?;
+        write_octave_marks(f, self.octave)
+    }
+}
+
+impl FromStr for TonicSolfa {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, octave) = parse_octave_marks(s.trim());
+        let body = body.to_lowercase();
+        let syllable = match body.as_str() {
+            "doh" => SolfegeSyllable::Do,
+            "ray" => SolfegeSyllable::Re,
+            "me" => SolfegeSyllable::Mi,
+            "fah" => SolfegeSyllable::Fa,
+            "soh" => SolfegeSyllable::So,
+            "lah" => SolfegeSyllable::La,
+            "te" => SolfegeSyllable::Ti,
+            body => [
+                SolfegeSyllable::Do,
+                SolfegeSyllable::Di,
+                SolfegeSyllable::Ra,
+                SolfegeSyllable::Re,
+                SolfegeSyllable::Ri,
+                SolfegeSyllable::Me,
+                SolfegeSyllable::Mi,
+                SolfegeSyllable::Fa,
+                SolfegeSyllable::Fi,
+                SolfegeSyllable::Se,
+                SolfegeSyllable::So,
+                SolfegeSyllable::Si,
+                SolfegeSyllable::Le,
+                SolfegeSyllable::La,
+                SolfegeSyllable::Li,
+                SolfegeSyllable::Te,
+                SolfegeSyllable::Ti,
+            ]
+            .into_iter()
+            .find(|&syllable| Self::new(syllable, 0).initials() == body)
+            .ok_or(())?,
+        };
+        Ok(Self::new(syllable, octave))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Accidental, Alphabet};
+
+    #[test]
+    fn test_tonic_sol_fa() {
+        let doh = Note::new(Alphabet::G, Accidental::Natural, 4);
+        let melody = ["d", "t,", "d", "fe", "s", "ta", "l", "d'"]
+            .map(|sol_fa| sol_fa.parse::<TonicSolfa>().unwrap());
+        let notes = melody
+            .iter()
+            .map(|sol_fa| sol_fa.to_note(&doh).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(notes[1].alphabet, Alphabet::F);
+        assert_eq!(notes[1].accidental, Accidental::Sharp);
+        assert!(notes[1].id() < notes[0].id());
+        assert_eq!(notes[3].accidental, Accidental::Sharp);
+        assert_eq!(notes[5].alphabet, Alphabet::F);
+        assert_eq!(notes[5].accidental, Accidental::Natural);
+        assert_eq!(TonicSolfa::interval(melody[0], melody[7]), Interval::OCTAVE);
+        for (note, sol_fa) in notes.iter().zip(melody) {
+            assert_eq!(TonicSolfa::from_note(note, &doh, Ordering::Greater), sol_fa);
+        }
+    }
+}